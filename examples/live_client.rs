@@ -0,0 +1,103 @@
+//! A small app that follows the editor's live push, run it next to the editor with
+//! `cargo run --example live_client` and enable "Push edits" on the Import / Export page.
+
+use iced::{
+    theme,
+    widget::{button, checkbox, column, container, row, text, text_input},
+    Application, Command, Element, Length, Settings, Subscription, Theme,
+};
+use theme_colors::live_client::{self, LiveTheme};
+
+fn main() -> iced::Result {
+    Preview::run(Settings::default())
+}
+
+struct Preview {
+    theme: Theme,
+    updates: usize,
+    input: String,
+    checked: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    ThemePushed(Box<LiveTheme>),
+    InputChanged(String),
+    Toggled(bool),
+    Pressed,
+}
+
+impl Application for Preview {
+    type Executor = iced::executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = ();
+
+    fn new(_flags: ()) -> (Self, Command<Message>) {
+        let preview = Self {
+            theme: Theme::Dark,
+            updates: 0,
+            input: String::new(),
+            checked: true,
+        };
+        (preview, Command::none())
+    }
+
+    fn title(&self) -> String {
+        "Live Theme Preview".to_string()
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::ThemePushed(live) => {
+                self.theme = live.to_theme();
+                self.updates += 1;
+            }
+            Message::InputChanged(input) => self.input = input,
+            Message::Toggled(checked) => self.checked = checked,
+            Message::Pressed => {}
+        }
+        Command::none()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let buttons = row!(
+            button(text("Primary")).on_press(Message::Pressed),
+            button(text("Secondary"))
+                .style(theme::Button::Secondary)
+                .on_press(Message::Pressed),
+            button(text("Positive"))
+                .style(theme::Button::Positive)
+                .on_press(Message::Pressed),
+            button(text("Destructive"))
+                .style(theme::Button::Destructive)
+                .on_press(Message::Pressed),
+        )
+        .spacing(10);
+
+        let content = column!(
+            text(format!("{} ({} updates)", self.theme, self.updates)).size(24),
+            buttons,
+            text_input("Text input", &self.input).on_input(Message::InputChanged),
+            checkbox("Checkbox", self.checked).on_toggle(Message::Toggled),
+        )
+        .spacing(20)
+        .max_width(500);
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        live_client::subscription(live_client::default_socket_path())
+            .map(|theme| Message::ThemePushed(Box::new(theme)))
+    }
+}
@@ -0,0 +1,137 @@
+//! Conversions between iced's `Palette` and `Extended` and the flat color arrays the editor and
+//! theme files use
+
+use iced::{
+    theme::{
+        palette::{Background, Danger, Extended, Pair, Primary, Secondary, Success},
+        Palette,
+    },
+    Color,
+};
+
+pub const fn palette_from_array(palette: &[Color; 5]) -> Palette {
+    Palette {
+        background: palette[0],
+        primary: palette[1],
+        text: palette[2],
+        success: palette[3],
+        danger: palette[4],
+    }
+}
+
+/// The Extended the editor builds for its custom theme, `is_dark` is what iced generates for the palette
+pub fn extended_from_colors(palette: Palette, extended: &[[Color; 2]; 15]) -> Extended {
+    extended_from_array(extended, Extended::generate(palette).is_dark)
+}
+
+pub const fn extended_from_array(extended: &[[Color; 2]; 15], is_dark: bool) -> Extended {
+    Extended {
+        background: Background {
+            base: Pair {
+                color: extended[0][0],
+                text: extended[0][1],
+            },
+            weak: Pair {
+                color: extended[1][0],
+                text: extended[1][1],
+            },
+            strong: Pair {
+                color: extended[2][0],
+                text: extended[2][1],
+            },
+        },
+        primary: Primary {
+            base: Pair {
+                color: extended[3][0],
+                text: extended[3][1],
+            },
+            weak: Pair {
+                color: extended[4][0],
+                text: extended[4][1],
+            },
+            strong: Pair {
+                color: extended[5][0],
+                text: extended[5][1],
+            },
+        },
+        secondary: Secondary {
+            base: Pair {
+                color: extended[6][0],
+                text: extended[6][1],
+            },
+            weak: Pair {
+                color: extended[7][0],
+                text: extended[7][1],
+            },
+            strong: Pair {
+                color: extended[8][0],
+                text: extended[8][1],
+            },
+        },
+        success: Success {
+            base: Pair {
+                color: extended[9][0],
+                text: extended[9][1],
+            },
+            weak: Pair {
+                color: extended[10][0],
+                text: extended[10][1],
+            },
+            strong: Pair {
+                color: extended[11][0],
+                text: extended[11][1],
+            },
+        },
+        danger: Danger {
+            base: Pair {
+                color: extended[12][0],
+                text: extended[12][1],
+            },
+            weak: Pair {
+                color: extended[13][0],
+                text: extended[13][1],
+            },
+            strong: Pair {
+                color: extended[14][0],
+                text: extended[14][1],
+            },
+        },
+        is_dark,
+    }
+}
+
+pub fn populate_palette_array(palette: Palette) -> [Color; 5] {
+    [
+        palette.background,
+        palette.primary,
+        palette.text,
+        palette.success,
+        palette.danger,
+    ]
+}
+
+pub fn populate_extended_array(palette: &Extended) -> [[Color; 2]; 15] {
+    [
+        [palette.background.base.color, palette.background.base.text],
+        [palette.background.weak.color, palette.background.weak.text],
+        [
+            palette.background.strong.color,
+            palette.background.strong.text,
+        ],
+        [palette.primary.base.color, palette.primary.base.text],
+        [palette.primary.weak.color, palette.primary.weak.text],
+        [palette.primary.strong.color, palette.primary.strong.text],
+        [palette.secondary.base.color, palette.secondary.base.text],
+        [palette.secondary.weak.color, palette.secondary.weak.text],
+        [
+            palette.secondary.strong.color,
+            palette.secondary.strong.text,
+        ],
+        [palette.success.base.color, palette.success.base.text],
+        [palette.success.weak.color, palette.success.weak.text],
+        [palette.success.strong.color, palette.success.strong.text],
+        [palette.danger.base.color, palette.danger.base.text],
+        [palette.danger.weak.color, palette.danger.weak.text],
+        [palette.danger.strong.color, palette.danger.strong.text],
+    ]
+}
@@ -0,0 +1,187 @@
+use std::{fmt, path::Path};
+
+use iced::{
+    theme::palette::Extended,
+    widget::{button, column, container, row, slider, text, text_input, Column, Row},
+    Alignment, Background, Color,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    arrays,
+    color::{self, Oklab},
+    document::{DocumentError, ThemeDocument},
+    split::{self, Side},
+    Message, ThemeColors,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendSpace {
+    Oklab,
+    Srgb,
+}
+
+impl fmt::Display for BlendSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlendSpace::Oklab => write!(f, "OKLab"),
+            BlendSpace::Srgb => write!(f, "sRGB"),
+        }
+    }
+}
+
+pub fn mix(from: Color, to: Color, ratio: f32, space: BlendSpace) -> Color {
+    let lerp = |a: f32, b: f32| a + (b - a) * ratio;
+    let alpha = lerp(from.a, to.a);
+
+    match space {
+        BlendSpace::Oklab => {
+            let from = Oklab::from_color(from);
+            let to = Oklab::from_color(to);
+            let mixed = Oklab {
+                l: lerp(from.l, to.l),
+                a: lerp(from.a, to.a),
+                b: lerp(from.b, to.b),
+            };
+            color::clamp_to_srgb(mixed.to_color(alpha))
+        }
+        BlendSpace::Srgb => Color {
+            r: lerp(from.r, to.r),
+            g: lerp(from.g, to.g),
+            b: lerp(from.b, to.b),
+            a: alpha,
+        },
+    }
+}
+
+/// Every palette and extended slot of `from` moved `ratio` of the way toward `to`
+pub fn blend(
+    from: &ThemeDocument,
+    to: &ThemeDocument,
+    ratio: f32,
+    space: BlendSpace,
+) -> ThemeDocument {
+    let mut palette = from.palette;
+    for (color, target) in palette.iter_mut().zip(to.palette) {
+        *color = mix(*color, target, ratio, space);
+    }
+
+    let mut extended = from.extended;
+    for (pair, target) in extended.iter_mut().zip(to.extended) {
+        for (color, target) in pair.iter_mut().zip(target) {
+            *color = mix(*color, target, ratio, space);
+        }
+    }
+
+    ThemeDocument {
+        name: format!("{} {:.0}% {}", from.name, ratio * 100.0, to.name),
+        is_dark: Extended::generate(arrays::palette_from_array(&palette)).is_dark,
+        palette,
+        extended,
+    }
+}
+
+/// Writes `steps` themes going from `from` to `to`, both included, as `<prefix>-<step>.toml`
+pub fn export_sequence(
+    from: &ThemeDocument,
+    to: &ThemeDocument,
+    steps: usize,
+    space: BlendSpace,
+    directory: &Path,
+    prefix: &str,
+) -> Result<usize, DocumentError> {
+    std::fs::create_dir_all(directory)?;
+
+    for step in 0..steps {
+        let ratio = if steps > 1 {
+            step as f32 / (steps - 1) as f32
+        } else {
+            0.0
+        };
+        let mut document = blend(from, to, ratio, space);
+        document.name = format!("{prefix}-{step:02}");
+        document.save(directory.join(format!("{}.toml", document.name)))?;
+    }
+
+    Ok(steps)
+}
+
+pub fn view(app: &ThemeColors) -> Column<'_, Message> {
+    let [from, to] = &app.blend_sources;
+
+    let sources = row!(
+        split::source_view(app, from, Side::Left, Message::BlendSource),
+        text("→"),
+        split::source_view(app, to, Side::Right, Message::BlendSource)
+    )
+    .spacing(20)
+    .align_items(Alignment::Center);
+
+    let ratio = row!(
+        text("Ratio").width(100),
+        slider(0.0..=1.0, app.blend_ratio, Message::BlendRatio)
+            .step(0.01)
+            .width(300),
+        text(format!("{:.0}%", app.blend_ratio * 100.0)).width(50)
+    )
+    .spacing(5);
+
+    let space = row!(
+        text("Interpolate in").width(100),
+        iced::widget::radio(
+            BlendSpace::Oklab.to_string(),
+            BlendSpace::Oklab,
+            Some(app.blend_space),
+            Message::SelectBlendSpace
+        ),
+        iced::widget::radio(
+            BlendSpace::Srgb.to_string(),
+            BlendSpace::Srgb,
+            Some(app.blend_space),
+            Message::SelectBlendSpace
+        )
+    )
+    .spacing(20);
+
+    let preview = blend(
+        &from.document,
+        &to.document,
+        app.blend_ratio,
+        app.blend_space,
+    );
+    let preview = preview
+        .palette
+        .into_iter()
+        .chain(preview.extended.iter().map(|pair| pair[0]))
+        .fold(Row::new(), |row, color| {
+            row.push(
+                container(column!())
+                    .width(20)
+                    .height(20)
+                    .style(container::Appearance {
+                        background: Some(Background::Color(color)),
+                        ..Default::default()
+                    }),
+            )
+        });
+
+    let sequence = row!(
+        button(text("Apply Blend")).on_press(Message::ApplyBlend),
+        text("Export").width(60),
+        text_input("Steps", &app.blend_steps)
+            .on_input(Message::BlendStepsChanged)
+            .width(60),
+        text("steps to"),
+        text_input("Directory", &app.blend_directory)
+            .on_input(Message::BlendDirectoryChanged)
+            .width(200),
+        button(text("Export Sequence")).on_press(Message::ExportBlendSequence)
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    column!(sources, ratio, space, preview, sequence)
+        .spacing(10)
+        .align_items(Alignment::Center)
+}
@@ -0,0 +1,166 @@
+//! Compiles theme files into the app from a build script, as an alternative to [`crate::runtime`].
+//!
+//! In `build.rs`, with `theme_colors` as a build dependency:
+//!
+//! ```no_run
+//! theme_colors::build::include_theme("themes/nord.toml");
+//! ```
+//!
+//! and in the app, which also depends on `theme_colors`:
+//!
+//! ```ignore
+//! mod nord {
+//!     theme_colors::include_theme!("nord");
+//! }
+//!
+//! let theme = nord::theme();
+//! ```
+//!
+//! The file is read with the same parser as the editor's Open. Invalid colors and palette text that
+//! does not reach [`MIN_CONTRAST`] against the palette background fail the build. Extended pairs
+//! below it are only reported as cargo warnings, iced's own generated pairs often are.
+
+use std::{env, fs, path::Path};
+
+use iced::Color;
+
+use crate::{color, document::ThemeDocument, Select};
+
+/// WCAG 2 AA for normal sized text
+pub const MIN_CONTRAST: f32 = 4.5;
+
+/// Expands to the items that [`include_theme`] generated for the theme file with this file stem:
+/// `NAME`, `IS_DARK`, `PALETTE`, `EXTENDED_COLORS`, `EXTENDED` and a `theme()` function
+#[macro_export]
+macro_rules! include_theme {
+    ($name:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $name, ".theme.rs"));
+    };
+}
+
+/// Checks the theme file and writes it as Rust to `$OUT_DIR/<file stem>.theme.rs`, panicking with
+/// the problems found so the build fails
+pub fn include_theme(path: impl AsRef<Path>) {
+    include_theme_with_contrast(path, MIN_CONTRAST);
+}
+
+/// [`include_theme`] with another minimum contrast ratio, for themes that are meant to have less
+/// contrast than WCAG 2 AA
+pub fn include_theme_with_contrast(path: impl AsRef<Path>, min_contrast: f32) {
+    let path = path.as_ref();
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let document = match ThemeDocument::load(path) {
+        Ok(document) => document,
+        Err(error) => panic!("{}: {}", path.display(), error),
+    };
+
+    for warning in contrast_problems(&document, extended_text_slots(), min_contrast) {
+        println!("cargo:warning={}: {}", path.display(), warning);
+    }
+    let problems = contrast_problems(&document, [PALETTE_TEXT], min_contrast);
+    if !problems.is_empty() {
+        panic!("{}:\n  {}", path.display(), problems.join("\n  "));
+    }
+
+    let stem = path
+        .file_stem()
+        .expect("theme path has a file name")
+        .to_string_lossy();
+    let out_dir = env::var_os("OUT_DIR").expect("include_theme is called from a build script");
+    let out_path = Path::new(&out_dir).join(format!("{stem}.theme.rs"));
+
+    if let Err(error) = fs::write(&out_path, to_rust(&document)) {
+        panic!("{}: {}", out_path.display(), error);
+    }
+}
+
+/// Palette text against the palette background and every Extended text against its color, only
+/// the palette fails [`include_theme`]
+pub fn check_contrast(document: &ThemeDocument, min_contrast: f32) -> Vec<String> {
+    contrast_problems(
+        document,
+        std::iter::once(PALETTE_TEXT).chain(extended_text_slots()),
+        min_contrast,
+    )
+}
+
+const PALETTE_TEXT: Select = Select::Palette(2);
+
+fn extended_text_slots() -> impl Iterator<Item = Select> {
+    (0..15).map(|index| Select::Extended((index, 1)))
+}
+
+fn contrast_problems(
+    document: &ThemeDocument,
+    text_slots: impl IntoIterator<Item = Select>,
+    min_contrast: f32,
+) -> Vec<String> {
+    text_slots
+        .into_iter()
+        .filter_map(|text| {
+            let background = text.contrast_partner();
+            let ratio = color::contrast_ratio(document.color(text), document.color(background));
+            (ratio < min_contrast).then(|| {
+                format!(
+                    "{} on {} has a contrast of {:.2}:1, at least {:.2}:1 is needed",
+                    text.name(),
+                    background.name(),
+                    ratio,
+                    min_contrast
+                )
+            })
+        })
+        .collect()
+}
+
+/// The theme as `const` items, `theme()` returns the same theme as [`ThemeDocument::to_theme`]
+pub fn to_rust(document: &ThemeDocument) -> String {
+    let is_dark = document.to_theme().extended_palette().is_dark;
+
+    let palette_fields: String = crate::PALETTE_NAMES
+        .iter()
+        .zip(document.palette)
+        .map(|(name, color)| format!("    {}: {},\n", name, color_literal(color)))
+        .collect();
+    let extended_pairs: String = document
+        .extended
+        .iter()
+        .map(|[color, text]| {
+            format!(
+                "    [{}, {}],\n",
+                color_literal(*color),
+                color_literal(*text)
+            )
+        })
+        .collect();
+
+    format!(
+        "// Generated by theme_colors::build from the theme file
+
+pub const NAME: &str = {name:?};
+pub const IS_DARK: bool = {is_dark};
+
+pub const PALETTE: ::iced::theme::Palette = ::iced::theme::Palette {{
+{palette_fields}}};
+
+pub const EXTENDED_COLORS: [[::iced::Color; 2]; 15] = [
+{extended_pairs}];
+
+pub const EXTENDED: ::iced::theme::palette::Extended =
+    ::theme_colors::arrays::extended_from_array(&EXTENDED_COLORS, IS_DARK);
+
+pub fn theme() -> ::iced::Theme {{
+    ::iced::Theme::custom_with_fn(NAME.to_string(), PALETTE, |_| EXTENDED)
+}}
+",
+        name = document.name,
+    )
+}
+
+fn color_literal(color: Color) -> String {
+    format!(
+        "::iced::Color {{ r: {:?}, g: {:?}, b: {:?}, a: {:?} }}",
+        color.r, color.g, color.b, color.a
+    )
+}
@@ -0,0 +1,59 @@
+use std::fs;
+
+use crate::{color::Gamut, document::ThemeDocument, share, style_guide};
+
+const USAGE: &str = "Usage:
+  theme_colors                                      start the editor
+  theme_colors style-guide <theme.toml> [out.html]  write an HTML style guide
+  theme_colors share <theme.toml>                   print a share code for the theme
+  theme_colors unshare <code> <theme.toml>          save a share code as a theme file";
+
+/// Runs a command line subcommand, `None` means no subcommand was given and the editor should start
+pub fn run(args: &[String]) -> Option<Result<String, String>> {
+    let (command, args) = args.split_first()?;
+
+    let result = match command.as_str() {
+        "style-guide" => style_guide(args),
+        "share" => share(args),
+        "unshare" => unshare(args),
+        "help" | "--help" | "-h" => Ok(USAGE.to_string()),
+        _ => Err(format!("Unknown command {command:?}\n\n{USAGE}")),
+    };
+    Some(result)
+}
+
+fn style_guide(args: &[String]) -> Result<String, String> {
+    let (theme, output) = match args {
+        [theme] => (theme, style_guide::FILE_NAME),
+        [theme, output] => (theme, output.as_str()),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let document = ThemeDocument::load(theme).map_err(|error| format!("{theme}: {error}"))?;
+    fs::write(output, style_guide::to_html(&document, Gamut::Srgb))
+        .map_err(|error| format!("{output}: {error}"))?;
+
+    Ok(format!("Wrote {output}"))
+}
+
+fn share(args: &[String]) -> Result<String, String> {
+    let [theme] = args else {
+        return Err(USAGE.to_string());
+    };
+
+    let document = ThemeDocument::load(theme).map_err(|error| format!("{theme}: {error}"))?;
+    Ok(share::encode(&document))
+}
+
+fn unshare(args: &[String]) -> Result<String, String> {
+    let [code, output] = args else {
+        return Err(USAGE.to_string());
+    };
+
+    let document = share::decode(code).map_err(|error| error.to_string())?;
+    document
+        .save(output)
+        .map_err(|error| format!("{output}: {error}"))?;
+
+    Ok(format!("Wrote {} to {output}", document.name))
+}
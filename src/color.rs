@@ -0,0 +1,646 @@
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    /// Hue in degrees, `0.0..360.0`
+    pub h: f32,
+}
+
+/// CIE XYZ relative to the D65 white point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Xyz {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// CIE L*a*b* relative to the D65 white point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+/// CIE LCh(ab), the cylindrical form of [`Lab`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lch {
+    pub l: f32,
+    pub c: f32,
+    /// Hue in degrees, `0.0..360.0`
+    pub h: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees, `0.0..360.0`
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    /// Hue in degrees, `0.0..360.0`
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub fn clamp_to_srgb(color: Color) -> Color {
+    Color {
+        r: color.r.clamp(0.0, 1.0),
+        g: color.g.clamp(0.0, 1.0),
+        b: color.b.clamp(0.0, 1.0),
+        a: color.a.clamp(0.0, 1.0),
+    }
+}
+
+/// WCAG 2 relative luminance, alpha is ignored
+pub fn relative_luminance(color: Color) -> f32 {
+    0.2126 * srgb_to_linear(color.r)
+        + 0.7152 * srgb_to_linear(color.g)
+        + 0.0722 * srgb_to_linear(color.b)
+}
+
+/// WCAG 2 contrast ratio, from `1.0` to `21.0`
+pub fn contrast_ratio(first: Color, second: Color) -> f32 {
+    let first = relative_luminance(first);
+    let second = relative_luminance(second);
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// APCA lightness contrast (Lc) of `text` on `background` following APCA-W3 0.0.98G-4g, about
+/// `-108.0..=106.0`, negative for light text on a dark background. Alpha is ignored.
+pub fn apca_contrast(text: Color, background: Color) -> f32 {
+    let luminance = |color: Color| {
+        let y = 0.2126729 * color.r.clamp(0.0, 1.0).powf(2.4)
+            + 0.7151522 * color.g.clamp(0.0, 1.0).powf(2.4)
+            + 0.072175 * color.b.clamp(0.0, 1.0).powf(2.4);
+        // Soft clamp near black
+        if y < 0.022 {
+            y + (0.022 - y).powf(1.414)
+        } else {
+            y
+        }
+    };
+    let text = luminance(text);
+    let background = luminance(background);
+
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+
+    let contrast = if background > text {
+        let sapc = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+        if sapc < 0.1 {
+            0.0
+        } else {
+            sapc - 0.027
+        }
+    } else {
+        let sapc = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+        if sapc > -0.1 {
+            0.0
+        } else {
+            sapc + 0.027
+        }
+    };
+
+    contrast * 100.0
+}
+
+/// Formats as `#rrggbb`, or `#rrggbbaa` when the color is not fully opaque
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == u8::MAX {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Parses `#rgb`, `#rrggbb` and `#rrggbbaa`, the leading `#` is optional
+pub fn from_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.is_ascii() {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok();
+
+    match hex.len() {
+        3 => {
+            let mut channels = hex.chars().map(|c| c.to_digit(16).map(|v| v as u8 * 17));
+            Some(Color::from_rgb8(
+                channels.next()??,
+                channels.next()??,
+                channels.next()??,
+            ))
+        }
+        6 => Some(Color::from_rgb8(channel(0)?, channel(1)?, channel(2)?)),
+        8 => Some(Color::from_rgba8(
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+            channel(3)? as f32 / 255.0,
+        )),
+        _ => None,
+    }
+}
+
+const D65: Xyz = Xyz {
+    x: 0.95047,
+    y: 1.0,
+    z: 1.08883,
+};
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+impl Xyz {
+    pub fn from_color(color: Color) -> Self {
+        let r = srgb_to_linear(color.r);
+        let g = srgb_to_linear(color.g);
+        let b = srgb_to_linear(color.b);
+
+        Self {
+            x: 0.4124564 * r + 0.3575761 * g + 0.1804375 * b,
+            y: 0.2126729 * r + 0.7151522 * g + 0.072175 * b,
+            z: 0.0193339 * r + 0.119192 * g + 0.9503041 * b,
+        }
+    }
+
+    /// The result is not clamped, channels outside of `0.0..=1.0` are out of the sRGB gamut
+    pub fn to_color(self, alpha: f32) -> Color {
+        let Xyz { x, y, z } = self;
+
+        Color {
+            r: linear_to_srgb(3.2404542 * x - 1.5371385 * y - 0.4985314 * z),
+            g: linear_to_srgb(-0.969266 * x + 1.8760108 * y + 0.041556 * z),
+            b: linear_to_srgb(0.0556434 * x - 0.2040259 * y + 1.0572252 * z),
+            a: alpha,
+        }
+    }
+}
+
+impl Lab {
+    pub fn from_color(color: Color) -> Self {
+        Self::from_xyz(Xyz::from_color(color))
+    }
+
+    pub fn from_xyz(xyz: Xyz) -> Self {
+        let f = |t: f32| {
+            if t > LAB_EPSILON {
+                t.cbrt()
+            } else {
+                (LAB_KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let fx = f(xyz.x / D65.x);
+        let fy = f(xyz.y / D65.y);
+        let fz = f(xyz.z / D65.z);
+
+        Self {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    pub fn to_xyz(self) -> Xyz {
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let f_inverse = |f: f32| {
+            if f.powi(3) > LAB_EPSILON {
+                f.powi(3)
+            } else {
+                (116.0 * f - 16.0) / LAB_KAPPA
+            }
+        };
+
+        Xyz {
+            x: D65.x * f_inverse(fx),
+            y: D65.y * f_inverse(fy),
+            z: D65.z * f_inverse(fz),
+        }
+    }
+
+    /// The result is not clamped, channels outside of `0.0..=1.0` are out of the sRGB gamut
+    pub fn to_color(self, alpha: f32) -> Color {
+        self.to_xyz().to_color(alpha)
+    }
+
+    pub fn to_lch(self) -> Lch {
+        Lch {
+            l: self.l,
+            c: self.a.hypot(self.b),
+            h: self.b.atan2(self.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl Lch {
+    pub fn from_color(color: Color) -> Self {
+        Lab::from_color(color).to_lch()
+    }
+
+    pub fn to_lab(self) -> Lab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        Lab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+    }
+
+    pub fn to_color(self, alpha: f32) -> Color {
+        self.to_lab().to_color(alpha)
+    }
+}
+
+/// Color difference according to CIE76, the euclidean distance in [`Lab`]
+pub fn delta_e_76(first: Color, second: Color) -> f32 {
+    let lab1 = Lab::from_color(first);
+    let lab2 = Lab::from_color(second);
+
+    ((lab1.l - lab2.l).powi(2) + (lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2)).sqrt()
+}
+
+/// Color difference according to CIEDE2000, `1.0` is roughly the smallest noticeable difference
+pub fn delta_e_2000(first: Color, second: Color) -> f32 {
+    let lab1 = Lab::from_color(first);
+    let lab2 = Lab::from_color(second);
+
+    let c1 = lab1.a.hypot(lab1.b);
+    let c2 = lab2.a.hypot(lab2.b);
+    let c_mean = (c1 + c2) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f32.powi(7))).sqrt());
+
+    let a1 = lab1.a * (1.0 + g);
+    let a2 = lab2.a * (1.0 + g);
+    let c1 = a1.hypot(lab1.b);
+    let c2 = a2.hypot(lab2.b);
+    let hue = |a: f32, b: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a1, lab1.b);
+    let h2 = hue(a2, lab2.b);
+
+    let delta_l = lab2.l - lab1.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_mean = (lab1.l + lab2.l) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + 25f32.powi(7))).sqrt();
+    let s_l = 1.0 + (0.015 * (l_mean - 50.0).powi(2)) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    ((delta_l / s_l).powi(2)
+        + (delta_c / s_c).powi(2)
+        + (delta_h / s_h).powi(2)
+        + r_t * (delta_c / s_c) * (delta_h / s_h))
+        .sqrt()
+}
+
+impl Hsl {
+    pub fn from_color(color: Color) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return Self { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == color.r {
+            ((color.g - color.b) / delta).rem_euclid(6.0)
+        } else if max == color.g {
+            (color.b - color.r) / delta + 2.0
+        } else {
+            (color.r - color.g) / delta + 4.0
+        };
+
+        Self { h: h * 60.0, s, l }
+    }
+
+    pub fn to_color(self, alpha: f32) -> Color {
+        let c = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let m = self.l - c / 2.0;
+
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        Color {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+            a: alpha,
+        }
+    }
+}
+
+impl Hsv {
+    pub fn from_color(color: Color) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let delta = max - min;
+
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        // Same hue as HSL
+        let h = Hsl::from_color(color).h;
+
+        Self { h, s, v: max }
+    }
+
+    pub fn to_color(self, alpha: f32) -> Color {
+        let l = self.v * (1.0 - self.s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (self.v - l) / l.min(1.0 - l)
+        };
+
+        Hsl { h: self.h, s, l }.to_color(alpha)
+    }
+}
+
+impl Oklab {
+    pub fn from_color(color: Color) -> Self {
+        let r = srgb_to_linear(color.r);
+        let g = srgb_to_linear(color.g);
+        let b = srgb_to_linear(color.b);
+
+        let l = (0.41222147 * r + 0.53633254 * g + 0.051445993 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+
+        Self {
+            l: 0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            a: 1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            b: 0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+        }
+    }
+
+    /// The result is not clamped, channels outside of `0.0..=1.0` are out of the sRGB gamut
+    pub fn to_color(self, alpha: f32) -> Color {
+        let l = (self.l + 0.39633778 * self.a + 0.21580376 * self.b).powi(3);
+        let m = (self.l - 0.105561346 * self.a - 0.06385417 * self.b).powi(3);
+        let s = (self.l - 0.08948418 * self.a - 1.2914855 * self.b).powi(3);
+
+        Color {
+            r: linear_to_srgb(4.0767417 * l - 3.3077116 * m + 0.23096994 * s),
+            g: linear_to_srgb(-1.268438 * l + 2.6097574 * m - 0.34131938 * s),
+            b: linear_to_srgb(-0.0041960863 * l - 0.7034186 * m + 1.7076147 * s),
+            a: alpha,
+        }
+    }
+
+    pub fn to_oklch(self) -> Oklch {
+        Oklch {
+            l: self.l,
+            c: self.a.hypot(self.b),
+            h: self.b.atan2(self.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl Oklch {
+    pub fn from_color(color: Color) -> Self {
+        Oklab::from_color(color).to_oklch()
+    }
+
+    pub fn to_oklab(self) -> Oklab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        Oklab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+    }
+
+    pub fn to_color(self, alpha: f32) -> Color {
+        self.to_oklab().to_color(alpha)
+    }
+}
+
+/// Display P3 channels of a color, P3 uses the same transfer function as sRGB
+pub fn to_display_p3(color: Color) -> [f32; 3] {
+    let Xyz { x, y, z } = Xyz::from_color(color);
+
+    [
+        linear_to_srgb(2.493497 * x - 0.9313836 * y - 0.4027108 * z),
+        linear_to_srgb(-0.829489 * x + 1.7626641 * y + 0.0236247 * z),
+        linear_to_srgb(0.0358458 * x - 0.0761724 * y + 0.9568845 * z),
+    ]
+}
+
+/// The result is not clamped, Display P3 colors outside of sRGB have channels outside of `0.0..=1.0`
+pub fn from_display_p3([r, g, b]: [f32; 3], alpha: f32) -> Color {
+    let [r, g, b] = [r, g, b].map(srgb_to_linear);
+
+    Xyz {
+        x: 0.4865709 * r + 0.2656677 * g + 0.1982173 * b,
+        y: 0.2289746 * r + 0.6917385 * g + 0.0792869 * b,
+        z: 0.0451134 * g + 1.0439444 * b,
+    }
+    .to_color(alpha)
+}
+
+/// The colors a display can show, colors are stored unclamped so they can be outside of sRGB
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gamut {
+    Srgb,
+    DisplayP3,
+}
+
+impl Gamut {
+    pub const ALL: [Gamut; 2] = [Gamut::Srgb, Gamut::DisplayP3];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Gamut::Srgb => "sRGB",
+            Gamut::DisplayP3 => "Display P3",
+        }
+    }
+
+    pub fn contains(self, color: Color) -> bool {
+        let channels = match self {
+            Gamut::Srgb => [color.r, color.g, color.b],
+            Gamut::DisplayP3 => to_display_p3(color),
+        };
+        channels
+            .iter()
+            .all(|channel| (-0.0001..=1.0001).contains(channel))
+    }
+
+    /// Lowers chroma at constant OKLCH lightness and hue until the color fits, clamping would shift the hue
+    pub fn map(self, color: Color) -> Color {
+        if self.contains(color) {
+            return color;
+        }
+        self.fit_chroma(Oklch::from_color(color), color.a)
+    }
+
+    /// The color with the highest chroma up to `lch.c` that fits, only rounding errors are clamped
+    pub fn fit_chroma(self, mut lch: Oklch, alpha: f32) -> Color {
+        let clamp = |color: Color| match self {
+            Gamut::Srgb => clamp_to_srgb(color),
+            Gamut::DisplayP3 => from_display_p3(
+                to_display_p3(color).map(|channel| channel.clamp(0.0, 1.0)),
+                color.a,
+            ),
+        };
+
+        let color = lch.to_color(alpha);
+        if self.contains(color) {
+            return clamp(color);
+        }
+
+        let (mut low, mut high) = (0.0, lch.c);
+        for _ in 0..20 {
+            lch.c = (low + high) / 2.0;
+            if self.contains(lch.to_color(alpha)) {
+                low = lch.c;
+            } else {
+                high = lch.c;
+            }
+        }
+        lch.c = low;
+
+        clamp(lch.to_color(alpha))
+    }
+}
+
+/// Color vision deficiencies, simulated with the full severity matrices of Machado et al. (2009)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 4] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+        Deficiency::Achromatopsia,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Deficiency::Protanopia => "Protanopia",
+            Deficiency::Deuteranopia => "Deuteranopia",
+            Deficiency::Tritanopia => "Tritanopia",
+            Deficiency::Achromatopsia => "Achromatopsia",
+        }
+    }
+
+    pub fn simulate(self, color: Color) -> Color {
+        let matrix = match self {
+            Deficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+            Deficiency::Achromatopsia => [[0.2126, 0.7152, 0.0722]; 3],
+        };
+
+        let linear = [
+            srgb_to_linear(color.r),
+            srgb_to_linear(color.g),
+            srgb_to_linear(color.b),
+        ];
+        let [r, g, b] = matrix.map(|row| {
+            let value = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+            linear_to_srgb(value.clamp(0.0, 1.0))
+        });
+
+        Color {
+            r,
+            g,
+            b,
+            a: color.a,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use iced::{
+    theme,
+    widget::{button, checkbox, column, container, row, text, Column},
+    Alignment, Background, Color, Element, Length,
+};
+
+use crate::{color, Message, Select, ThemeColors};
+
+/// Differences below this are rounding noise from the 8 bit inputs
+const CHANGED_THRESHOLD: f32 = 0.01;
+/// Half of an 8 bit alpha step, ΔE2000 does not see alpha so it is compared on its own
+const ALPHA_THRESHOLD: f32 = 0.5 / 255.0;
+
+fn is_changed(original: Color, edited: Color) -> bool {
+    color::delta_e_2000(original, edited) > CHANGED_THRESHOLD
+        || (edited.a - original.a).abs() > ALPHA_THRESHOLD
+}
+
+pub fn view(app: &ThemeColors) -> Element<'_, Message> {
+    let slots: Vec<(Select, Color, Color, f32)> = Select::all()
+        .map(|select| {
+            let original = app.working_color(select);
+            let edited = app.color(select);
+            (
+                select,
+                original,
+                edited,
+                color::delta_e_2000(original, edited),
+            )
+        })
+        .collect();
+
+    let changed = slots
+        .iter()
+        .filter(|(_, original, edited, _)| is_changed(*original, *edited))
+        .count();
+
+    let header = row!(
+        text(format!(
+            "{changed} of {} colors differ from {}",
+            slots.len(),
+            app.working_theme
+        ))
+        .width(Length::Fill),
+        checkbox("Changed only", app.compare_changed_only).on_toggle(Message::ToggleChangedOnly)
+    )
+    .spacing(20)
+    .align_items(Alignment::Center);
+
+    let labels = row!(
+        text("Slot").width(250),
+        text("Original").width(90),
+        text("Edited").width(90),
+        text("ΔE2000").width(80),
+        text("Δ alpha").width(80),
+    )
+    .spacing(10);
+
+    let mut rows = Column::new().spacing(4);
+    for (select, original, edited, delta_e) in slots {
+        let is_changed = is_changed(original, edited);
+        if app.compare_changed_only && !is_changed {
+            continue;
+        }
+
+        let name = if is_changed {
+            text(select.name()).style(theme::Text::Color(
+                app.app_theme.extended_palette().primary.base.color,
+            ))
+        } else {
+            text(select.name())
+        };
+
+        rows = rows.push(
+            row!(
+                name.width(250),
+                compare_swatch(original),
+                compare_swatch(edited),
+                text(format!("{delta_e:.2}")).width(80),
+                text(format!("{:+.2}", edited.a - original.a)).width(80),
+                button(text("Revert"))
+                    .on_press_maybe(is_changed.then_some(Message::RevertSlot(select)))
+            )
+            .spacing(10)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    column!(header, labels, rows).spacing(10).width(690).into()
+}
+
+fn compare_swatch<'a>(color: Color) -> Element<'a, Message> {
+    container(column!())
+        .width(90)
+        .height(24)
+        .style(container::Appearance {
+            background: Some(Background::Color(color)),
+            ..Default::default()
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alpha_only_edits_are_changes() {
+        let original = Color::from_rgb8(0x5e, 0x7c, 0xe2);
+
+        assert!(!is_changed(original, original));
+        assert!(!is_changed(
+            original,
+            Color::from_rgb(original.r, original.g, original.b)
+        ));
+        assert!(is_changed(original, Color { a: 0.5, ..original }));
+        assert!(is_changed(
+            original,
+            Color {
+                a: 254.0 / 255.0,
+                ..original
+            }
+        ));
+        assert!(is_changed(original, Color::from_rgb8(0x5e, 0x7c, 0xe0)));
+    }
+}
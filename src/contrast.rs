@@ -0,0 +1,427 @@
+use iced::{
+    theme,
+    widget::{checkbox, column, container, mouse_area, radio, row, scrollable, text, Column, Row},
+    Alignment, Background, Color, Element, Length,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{color, Message, Select, ThemeColors};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContrastMethod {
+    Wcag2,
+    Apca,
+}
+
+impl ContrastMethod {
+    pub const ALL: [ContrastMethod; 2] = [ContrastMethod::Wcag2, ContrastMethod::Apca];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ContrastMethod::Wcag2 => "WCAG 2",
+            ContrastMethod::Apca => "APCA",
+        }
+    }
+
+    /// The WCAG 2 ratio, or the APCA Lc which is signed and depends on which color is the text
+    pub fn measure(self, text: Color, background: Color) -> f32 {
+        match self {
+            ContrastMethod::Wcag2 => color::contrast_ratio(text, background),
+            ContrastMethod::Apca => color::apca_contrast(text, background),
+        }
+    }
+
+    pub fn format(self, value: f32) -> String {
+        match self {
+            ContrastMethod::Wcag2 => format!("{value:.2}:1"),
+            ContrastMethod::Apca => format!("Lc {value:.1}"),
+        }
+    }
+
+    fn thresholds(self) -> [f32; 3] {
+        match self {
+            ContrastMethod::Wcag2 => [3.0, 4.5, 7.0],
+            ContrastMethod::Apca => [45.0, 60.0, 75.0],
+        }
+    }
+
+    /// Names of the grades
+    pub fn levels(self) -> [&'static str; 4] {
+        match self {
+            ContrastMethod::Wcag2 => ["Fail", "AA large", "AA", "AAA"],
+            ContrastMethod::Apca => ["Fail", "Large text", "Content text", "Body text"],
+        }
+    }
+
+    /// `0` fails, `3` is fine for any text
+    pub fn grade(self, value: f32) -> usize {
+        let value = value.abs();
+        self.thresholds()
+            .iter()
+            .filter(|threshold| value >= **threshold)
+            .count()
+    }
+
+    pub fn level(self, value: f32) -> &'static str {
+        self.levels()[self.grade(value)]
+    }
+}
+
+/// Filters of the matrix, the palette and then the Extended roles
+pub const ROLE_FILTERS: [&str; 6] = [
+    "Palette",
+    "Background",
+    "Primary",
+    "Secondary",
+    "Success",
+    "Danger",
+];
+
+fn role(select: Select) -> usize {
+    match select {
+        Select::Palette(_) => 0,
+        Select::Extended((index, _)) => 1 + index / 3,
+    }
+}
+
+/// Weights of the columns in `FONT_SIZES`
+const FONT_WEIGHTS: [u16; 9] = [100, 200, 300, 400, 500, 600, 700, 800, 900];
+/// Not readable at any size
+const NO_TEXT: f32 = 999.0;
+/// Only for non-text elements like dividers and focus rings
+const NON_TEXT: f32 = 777.0;
+
+/// Smallest font size in px per weight for an absolute Lc, the APCA-W3 font lookup table (0.1.7).
+/// Each row applies from its Lc up to the next row.
+const FONT_SIZES: [(f32, [f32; 9]); 23] = [
+    (0.0, [NO_TEXT; 9]),
+    (15.0, [NON_TEXT; 9]),
+    (
+        25.0,
+        [
+            NON_TEXT, NON_TEXT, NON_TEXT, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0,
+        ],
+    ),
+    (
+        30.0,
+        [
+            NON_TEXT, NON_TEXT, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0,
+        ],
+    ),
+    (
+        35.0,
+        [NON_TEXT, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0],
+    ),
+    (
+        40.0,
+        [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0],
+    ),
+    (
+        45.0,
+        [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0],
+    ),
+    (50.0, [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0]),
+    (55.0, [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0]),
+    (60.0, [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0]),
+    (
+        65.0,
+        [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0],
+    ),
+    (70.0, [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0]),
+    (75.0, [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0]),
+    (
+        80.0,
+        [56.0, 38.25, 23.0, 17.25, 15.81, 14.81, 14.0, 16.0, 18.0],
+    ),
+    (
+        85.0,
+        [52.0, 34.5, 22.0, 16.5, 15.625, 14.625, 14.0, 16.0, 18.0],
+    ),
+    (90.0, [48.0, 32.0, 21.0, 16.0, 15.5, 14.5, 14.0, 16.0, 18.0]),
+    (95.0, [45.0, 28.0, 19.5, 15.5, 15.0, 14.0, 13.5, 16.0, 18.0]),
+    (
+        100.0,
+        [42.0, 26.5, 18.5, 15.0, 14.5, 13.5, 13.0, 16.0, 18.0],
+    ),
+    (
+        105.0,
+        [39.0, 25.0, 18.0, 14.5, 14.0, 13.0, 12.0, 16.0, 18.0],
+    ),
+    (
+        110.0,
+        [36.0, 24.0, 18.0, 14.0, 13.0, 12.0, 11.0, 16.0, 18.0],
+    ),
+    (
+        115.0,
+        [34.5, 22.5, 17.25, 12.5, 11.875, 11.25, 10.625, 14.5, 16.5],
+    ),
+    (
+        120.0,
+        [33.0, 21.0, 16.5, 11.0, 11.0, 11.0, 10.0, 13.0, 15.0],
+    ),
+    (
+        125.0,
+        [32.0, 20.0, 16.0, 10.0, 10.0, 10.0, 10.0, 12.0, 14.0],
+    ),
+];
+
+/// The smallest font size for the weight at this Lc, `None` when the contrast is too low for text
+pub fn min_font_size(lc: f32, weight: u16) -> Option<f32> {
+    let column = FONT_WEIGHTS.iter().position(|w| *w == weight)?;
+    let (_, sizes) = FONT_SIZES
+        .iter()
+        .take_while(|(from, _)| lc.abs() >= *from)
+        .last()?;
+    let size = sizes[column];
+    (size < NON_TEXT).then_some(size)
+}
+
+/// Minimum sizes for regular and bold text, read from the APCA lookup table
+pub fn font_recommendation(lc: f32) -> String {
+    match (min_font_size(lc, 400), min_font_size(lc, 700)) {
+        (Some(regular), Some(bold)) => format!("{regular}px regular, {bold}px bold"),
+        (None, Some(bold)) => format!("{bold}px bold only"),
+        _ if lc.abs() >= FONT_SIZES[1].0 => "Non-text only".to_string(),
+        _ => "Not readable".to_string(),
+    }
+}
+
+/// Every Extended pair and the palette text on the palette background, as `(label, text, background)`
+fn pairs() -> impl Iterator<Item = (String, Select, Select)> {
+    std::iter::once((
+        "palette text / background".to_string(),
+        Select::Palette(2),
+        Select::Palette(0),
+    ))
+    .chain((0..15).map(|index| {
+        let color = Select::Extended((index, 0));
+        let name = color.name();
+        let name = name
+            .trim_start_matches("extended.")
+            .trim_end_matches(".color");
+        (name.replace('.', " "), Select::Extended((index, 1)), color)
+    }))
+}
+
+pub fn method_picker(app: &ThemeColors) -> Element<'_, Message> {
+    ContrastMethod::ALL
+        .iter()
+        .fold(row!(text("Contrast")).spacing(20), |row, method| {
+            row.push(radio(
+                method.name(),
+                *method,
+                Some(app.contrast_method),
+                Message::SelectContrastMethod,
+            ))
+        })
+        .align_items(Alignment::Center)
+        .into()
+}
+
+pub fn view(app: &ThemeColors) -> Element<'_, Message> {
+    let method = app.contrast_method;
+
+    let labels = row!(
+        text("Pair").width(200),
+        text("Sample").width(90),
+        text(method.name()).width(90),
+        text("Level").width(110),
+        text("APCA font sizes").width(220),
+    )
+    .spacing(10);
+
+    let mut rows = Column::new().spacing(4);
+    for (label, text_slot, background_slot) in pairs() {
+        let text_color = app.color(text_slot);
+        let background = app.color(background_slot);
+        let value = method.measure(text_color, background);
+        let lc = color::apca_contrast(text_color, background);
+
+        let level = if method.grade(value) == 0 {
+            text(method.level(value)).style(theme::Text::Color(
+                app.app_theme.extended_palette().danger.base.color,
+            ))
+        } else {
+            text(method.level(value))
+        };
+
+        rows = rows.push(
+            row!(
+                text(label).width(200),
+                sample(text_color, background),
+                text(method.format(value)).width(90),
+                level.width(110),
+                text(font_recommendation(lc)).width(220),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    column!(
+        method_picker(app),
+        labels,
+        rows,
+        text("All colors").size(18),
+        matrix_view(app)
+    )
+    .spacing(10)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+/// Every shown color as text (rows) on every other color (columns). Clicking a cell edits the text
+/// color, right clicking edits the background.
+fn matrix_view(app: &ThemeColors) -> Column<'_, Message> {
+    let method = app.contrast_method;
+    let palette = app.app_theme.extended_palette();
+    let grade_pairs = [
+        palette.danger.base,
+        palette.background.strong,
+        palette.success.weak,
+        palette.success.strong,
+    ];
+
+    let filters = ROLE_FILTERS.iter().enumerate().fold(
+        row!(text("Roles")).spacing(20),
+        |row, (index, name)| {
+            row.push(
+                checkbox(*name, app.contrast_roles[index])
+                    .on_toggle(move |shown| Message::ToggleContrastRole(index, shown)),
+            )
+        },
+    );
+
+    let legend =
+        grade_pairs
+            .iter()
+            .enumerate()
+            .fold(Row::new().spacing(10), |row, (grade, pair)| {
+                let label = text(method.levels()[grade]).style(theme::Text::Color(pair.text));
+                row.push(cell(label, pair.color).width(110))
+            });
+
+    let hovered = match app.contrast_hover {
+        Some((text_slot, background_slot)) if text_slot == background_slot => text_slot.name(),
+        Some((text_slot, background_slot)) => {
+            let value = method.measure(app.color(text_slot), app.color(background_slot));
+            format!(
+                "{} on {}: {} {}",
+                text_slot.name(),
+                background_slot.name(),
+                method.format(value),
+                method.level(value)
+            )
+        }
+        None => {
+            "Click a cell to edit the text color, right click to edit the background".to_string()
+        }
+    };
+
+    let slots: Vec<Select> = Select::all()
+        .filter(|select| app.contrast_roles[role(*select)])
+        .collect();
+
+    let mut header = Row::new().spacing(2).push(container(text("")).width(200));
+    for background_slot in &slots {
+        header = header.push(
+            mouse_area(
+                container(text(""))
+                    .width(40)
+                    .height(22)
+                    .style(swatch_style(app.color(*background_slot))),
+            )
+            .on_press(Message::EditColor(*background_slot))
+            .on_enter(Message::HoverContrastCell(
+                *background_slot,
+                *background_slot,
+            )),
+        );
+    }
+
+    let mut matrix = Column::new().spacing(2).push(header);
+    for text_slot in &slots {
+        let text_color = app.color(*text_slot);
+        let name = text_slot.name();
+        let name = name
+            .trim_start_matches("palette.")
+            .trim_start_matches("extended.");
+
+        let mut line = Row::new().spacing(2).push(
+            mouse_area(
+                container(text(name.to_string()).size(12))
+                    .width(200)
+                    .height(22)
+                    .center_y(),
+            )
+            .on_press(Message::EditColor(*text_slot)),
+        );
+
+        for background_slot in &slots {
+            if text_slot == background_slot {
+                line = line.push(cell(text(""), palette.background.weak.color).width(40));
+                continue;
+            }
+
+            let value = method.measure(text_color, app.color(*background_slot));
+            let pair = grade_pairs[method.grade(value)];
+            let label = match method {
+                ContrastMethod::Wcag2 => format!("{value:.1}"),
+                ContrastMethod::Apca => format!("{:.0}", value.abs()),
+            };
+            line = line.push(
+                mouse_area(
+                    cell(text(label).style(theme::Text::Color(pair.text)), pair.color).width(40),
+                )
+                .on_press(Message::EditColor(*text_slot))
+                .on_right_press(Message::EditColor(*background_slot))
+                .on_enter(Message::HoverContrastCell(*text_slot, *background_slot)),
+            );
+        }
+        matrix = matrix.push(line);
+    }
+
+    column!(
+        filters.align_items(Alignment::Center),
+        legend,
+        text(hovered),
+        scrollable(matrix)
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::default()
+            ))
+            .width(Length::Fill),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center)
+}
+
+fn cell<'a>(
+    content: impl Into<Element<'a, Message>>,
+    background: Color,
+) -> container::Container<'a, Message> {
+    container(content)
+        .height(22)
+        .center_x()
+        .center_y()
+        .style(swatch_style(background))
+}
+
+fn swatch_style(color: Color) -> container::Appearance {
+    container::Appearance {
+        background: Some(Background::Color(color)),
+        ..Default::default()
+    }
+}
+
+fn sample<'a>(text_color: Color, background: Color) -> Element<'a, Message> {
+    container(text("Aa Text").style(theme::Text::Color(text_color)))
+        .width(90)
+        .height(24)
+        .center_x()
+        .center_y()
+        .style(container::Appearance {
+            background: Some(Background::Color(background)),
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,201 @@
+use std::{fmt, fs, io, path::Path};
+
+use iced::{Color, Theme};
+use serde::{Deserialize, Serialize};
+
+use crate::{arrays, color, Select};
+
+/// A theme as it is saved to disk, the colors are stored in the same layout as the editor arrays
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeDocument {
+    pub name: String,
+    pub is_dark: bool,
+    pub palette: [Color; 5],
+    pub extended: [[Color; 2]; 15],
+}
+
+#[derive(Debug)]
+pub enum DocumentError {
+    Io(io::Error),
+    Parse(String),
+    InvalidColor { slot: String, value: String },
+    MissingColor(String),
+}
+
+impl fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentError::Io(error) => write!(f, "{}", error),
+            DocumentError::Parse(error) => write!(f, "{}", error),
+            DocumentError::InvalidColor { slot, value } => {
+                write!(f, "{} is not a valid color: {:?}", slot, value)
+            }
+            DocumentError::MissingColor(slot) => write!(f, "{} is missing", slot),
+        }
+    }
+}
+
+impl std::error::Error for DocumentError {}
+
+impl From<io::Error> for DocumentError {
+    fn from(error: io::Error) -> Self {
+        DocumentError::Io(error)
+    }
+}
+
+impl ThemeDocument {
+    pub fn from_theme(theme: &Theme) -> Self {
+        let extended = theme.extended_palette();
+        Self {
+            name: theme.to_string(),
+            is_dark: extended.is_dark,
+            palette: arrays::populate_palette_array(theme.palette()),
+            extended: arrays::populate_extended_array(extended),
+        }
+    }
+
+    pub fn to_theme(&self) -> Theme {
+        let palette = arrays::palette_from_array(&self.palette);
+
+        Theme::custom_with_fn(self.name.clone(), palette, |_| {
+            arrays::extended_from_array(&self.extended, self.is_dark)
+        })
+    }
+
+    pub fn color(&self, select: Select) -> Color {
+        match select {
+            Select::Palette(index) => self.palette[index],
+            Select::Extended((index1, index2)) => self.extended[index1][index2],
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DocumentError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DocumentError> {
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let pair = |index: usize| PairFile {
+            color: color::to_hex(self.extended[index][0]),
+            text: color::to_hex(self.extended[index][1]),
+        };
+        let role = |role: usize| RoleFile {
+            base: pair(role * 3),
+            weak: pair(role * 3 + 1),
+            strong: pair(role * 3 + 2),
+        };
+
+        let file = ThemeFile {
+            name: self.name.clone(),
+            is_dark: self.is_dark,
+            palette: PaletteFile {
+                background: color::to_hex(self.palette[0]),
+                primary: color::to_hex(self.palette[1]),
+                text: color::to_hex(self.palette[2]),
+                success: color::to_hex(self.palette[3]),
+                danger: color::to_hex(self.palette[4]),
+            },
+            extended: ExtendedFile {
+                background: role(0),
+                primary: role(1),
+                secondary: role(2),
+                success: role(3),
+                danger: role(4),
+            },
+        };
+
+        toml::to_string(&file).expect("theme file serializes to toml")
+    }
+
+    pub fn from_toml(input: &str) -> Result<Self, DocumentError> {
+        let file: ThemeFile =
+            toml::from_str(input).map_err(|error| DocumentError::Parse(error.to_string()))?;
+
+        let palette_values = [
+            &file.palette.background,
+            &file.palette.primary,
+            &file.palette.text,
+            &file.palette.success,
+            &file.palette.danger,
+        ];
+        let mut palette = [Color::BLACK; 5];
+        for (index, value) in palette_values.into_iter().enumerate() {
+            palette[index] = parse_color(&Select::Palette(index).name(), value)?;
+        }
+
+        let roles = [
+            &file.extended.background,
+            &file.extended.primary,
+            &file.extended.secondary,
+            &file.extended.success,
+            &file.extended.danger,
+        ];
+        let mut extended = [[Color::BLACK; 2]; 15];
+        for (role, role_file) in roles.into_iter().enumerate() {
+            let strengths = [&role_file.base, &role_file.weak, &role_file.strong];
+            for (strength, pair) in strengths.into_iter().enumerate() {
+                let index = role * 3 + strength;
+                extended[index][0] =
+                    parse_color(&Select::Extended((index, 0)).name(), &pair.color)?;
+                extended[index][1] = parse_color(&Select::Extended((index, 1)).name(), &pair.text)?;
+            }
+        }
+
+        Ok(Self {
+            name: file.name,
+            is_dark: file.is_dark,
+            palette,
+            extended,
+        })
+    }
+}
+
+fn parse_color(slot: &str, value: &str) -> Result<Color, DocumentError> {
+    color::from_hex(value).ok_or_else(|| DocumentError::InvalidColor {
+        slot: slot.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThemeFile {
+    name: String,
+    is_dark: bool,
+    palette: PaletteFile,
+    extended: ExtendedFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PaletteFile {
+    background: String,
+    primary: String,
+    text: String,
+    success: String,
+    danger: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExtendedFile {
+    background: RoleFile,
+    primary: RoleFile,
+    secondary: RoleFile,
+    success: RoleFile,
+    danger: RoleFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RoleFile {
+    base: PairFile,
+    weak: PairFile,
+    strong: PairFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PairFile {
+    color: String,
+    text: String,
+}
@@ -0,0 +1,103 @@
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input, Row},
+    Alignment, Element,
+};
+
+use crate::{
+    frameworks::FrameworkFormat, swatch_sheet::SheetFormat, terminal, tokens::TokenFormat, Message,
+    ThemeColors,
+};
+
+pub fn view(app: &ThemeColors) -> Element<'_, Message> {
+    let export = column!(
+        text("Export").size(18),
+        text_input("Export directory", &app.export_directory)
+            .on_input(Message::ExportDirectoryChanged)
+            .width(400),
+        row!(
+            button(text("W3C Design Tokens")).on_press(Message::ExportTokens(TokenFormat::W3c)),
+            button(text("Tokens Studio"))
+                .on_press(Message::ExportTokens(TokenFormat::TokensStudio)),
+        )
+        .spacing(10),
+        FrameworkFormat::ALL
+            .iter()
+            .fold(Row::new().spacing(10), |row, format| {
+                row.push(button(text(format.label())).on_press(Message::ExportFramework(*format)))
+            }),
+        row!(
+            button(text("Swatch Sheet SVG")).on_press(Message::ExportSwatchSheet(SheetFormat::Svg)),
+            button(text("Swatch Sheet PNG")).on_press(Message::ExportSwatchSheet(SheetFormat::Png)),
+            button(text("Style Guide HTML")).on_press(Message::ExportStyleGuide),
+        )
+        .spacing(10),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let import = column!(
+        text("Import").size(18),
+        text_input("File to import", &app.import_path)
+            .on_input(Message::ImportPathChanged)
+            .width(400),
+        row!(
+            button(text("Design Tokens")).on_press(Message::ImportTokens),
+            button(text("Terminal / Editor Scheme")).on_press(Message::ImportScheme),
+            button(text("Rust Source")).on_press(Message::ImportRustSource),
+        )
+        .spacing(10),
+        text("W3C Design Tokens and Tokens Studio files are both recognized").size(14),
+        text("Schemes: Alacritty .toml, Kitty .conf, iTerm2 .itermcolors, Windows Terminal and VS Code .json").size(14),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let share = column!(
+        text("Share Code").size(18),
+        row!(
+            text_input("Paste a share code", &app.share_code)
+                .on_input(Message::ShareCodeChanged)
+                .on_submit(Message::ImportShareCode)
+                .width(400),
+            button(text("Import Share Code")).on_press(Message::ImportShareCode),
+            button(text("Copy Share Code")).on_press(Message::CopyShareCode),
+        )
+        .spacing(10),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let live = match &app.live_server {
+        Some(server) => format!(
+            "{} connected to {}",
+            server.client_count(),
+            server.path.display()
+        ),
+        None => "Running apps can follow edits through a local socket".to_string(),
+    };
+    let live = column!(
+        text("Live Push").size(18),
+        row!(
+            checkbox("Push edits", app.live_server.is_some()).on_toggle(Message::ToggleLiveServer),
+            text(live).size(14),
+        )
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let mut content = column!(export, import, share, live)
+        .spacing(30)
+        .align_items(Alignment::Center);
+    if !app.import_report.is_empty() {
+        content = content.push(app.import_report.iter().fold(
+            column!(text("Not imported from the Rust source").size(18)).spacing(4),
+            |report, line| report.push(text(line).size(14)),
+        ));
+    }
+    if let Some(mapping) = &app.scheme_import {
+        content = content.push(terminal::mapping_view(mapping));
+    }
+    content.into()
+}
@@ -0,0 +1,322 @@
+use iced::Color;
+
+use crate::{
+    color, document::ThemeDocument, Select, PAIR_NAMES, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameworkFormat {
+    Egui,
+    Slint,
+    Ratatui,
+    Bevy,
+}
+
+impl FrameworkFormat {
+    pub const ALL: [FrameworkFormat; 4] = [
+        FrameworkFormat::Egui,
+        FrameworkFormat::Slint,
+        FrameworkFormat::Ratatui,
+        FrameworkFormat::Bevy,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FrameworkFormat::Egui => "egui Visuals",
+            FrameworkFormat::Slint => "Slint Palette",
+            FrameworkFormat::Ratatui => "ratatui",
+            FrameworkFormat::Bevy => "Bevy",
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FrameworkFormat::Egui => "egui_visuals.rs",
+            FrameworkFormat::Slint => "palette.slint",
+            FrameworkFormat::Ratatui => "ratatui_theme.rs",
+            FrameworkFormat::Bevy => "bevy_theme.rs",
+        }
+    }
+
+    pub fn export(self, document: &ThemeDocument) -> String {
+        match self {
+            FrameworkFormat::Egui => to_egui(document),
+            FrameworkFormat::Slint => to_slint(document),
+            FrameworkFormat::Ratatui => to_ratatui(document),
+            FrameworkFormat::Bevy => to_bevy(document),
+        }
+    }
+}
+
+/// Name parts of a slot, `background` for the palette and `primary_weak_text` style for Extended
+fn slot_words(select: Select) -> Vec<&'static str> {
+    match select {
+        Select::Palette(index) => vec![PALETTE_NAMES[index]],
+        Select::Extended((index, 0)) => vec![ROLE_NAMES[index / 3], STRENGTH_NAMES[index % 3]],
+        Select::Extended((index, pair)) => vec![
+            ROLE_NAMES[index / 3],
+            STRENGTH_NAMES[index % 3],
+            PAIR_NAMES[pair],
+        ],
+    }
+}
+
+fn const_name(select: Select) -> String {
+    slot_words(select).join("_").to_uppercase()
+}
+
+fn header(document: &ThemeDocument, comment: &str, mapping: &[&str]) -> String {
+    let mut header = format!("{comment} Generated from the \"{}\" theme\n", document.name);
+    header.push_str(&format!("{comment}\n{comment} Role mapping:\n"));
+    for line in mapping {
+        header.push_str(&format!("{comment}   {line}\n"));
+    }
+    header
+}
+
+/// egui has no notion of roles, so the Extended pairs are spread over the widget states
+const EGUI_MAPPING: [&str; 10] = [
+    "panel_fill, window_fill           <- background.base.color",
+    "faint_bg_color, code_bg_color     <- background.weak.color",
+    "extreme_bg_color, window_stroke   <- background.strong.color",
+    "override_text_color               <- palette.text",
+    "hyperlink_color, selection        <- primary.base",
+    "widgets.noninteractive            <- background.base",
+    "widgets.inactive                  <- secondary.base",
+    "widgets.hovered                   <- secondary.strong",
+    "widgets.active                    <- primary.strong, widgets.open <- secondary.weak",
+    "warn_fg_color <- danger.weak.color, error_fg_color <- danger.base.color",
+];
+
+fn to_egui(document: &ThemeDocument) -> String {
+    let mut rust = header(document, "//", &EGUI_MAPPING);
+    rust.push_str("use egui::{Color32, Stroke, Visuals};\n\n");
+
+    for select in Select::all() {
+        rust.push_str(&format!(
+            "pub const {}: Color32 = {};\n",
+            const_name(select),
+            egui_color(document.color(select))
+        ));
+    }
+
+    let visuals = if document.is_dark { "dark" } else { "light" };
+    rust.push_str(&format!(
+        "
+pub fn visuals() -> Visuals {{
+    let mut visuals = Visuals::{visuals}();
+
+    visuals.override_text_color = Some(TEXT);
+    visuals.panel_fill = BACKGROUND_BASE;
+    visuals.window_fill = BACKGROUND_BASE;
+    visuals.faint_bg_color = BACKGROUND_WEAK;
+    visuals.code_bg_color = BACKGROUND_WEAK;
+    visuals.extreme_bg_color = BACKGROUND_STRONG;
+    visuals.window_stroke = Stroke::new(1.0, BACKGROUND_STRONG);
+    visuals.hyperlink_color = PRIMARY_BASE;
+    visuals.warn_fg_color = DANGER_WEAK;
+    visuals.error_fg_color = DANGER_BASE;
+    visuals.selection.bg_fill = PRIMARY_BASE;
+    visuals.selection.stroke = Stroke::new(1.0, PRIMARY_BASE_TEXT);
+
+    let widgets = &mut visuals.widgets;
+    for (state, fill, text) in [
+        (&mut widgets.noninteractive, BACKGROUND_BASE, BACKGROUND_BASE_TEXT),
+        (&mut widgets.inactive, SECONDARY_BASE, SECONDARY_BASE_TEXT),
+        (&mut widgets.hovered, SECONDARY_STRONG, SECONDARY_STRONG_TEXT),
+        (&mut widgets.active, PRIMARY_STRONG, PRIMARY_STRONG_TEXT),
+        (&mut widgets.open, SECONDARY_WEAK, SECONDARY_WEAK_TEXT),
+    ] {{
+        state.bg_fill = fill;
+        state.weak_bg_fill = fill;
+        state.fg_stroke = Stroke::new(1.0, text);
+    }}
+
+    visuals
+}}
+"
+    ));
+    rust
+}
+
+/// A `const` constructor, `from_rgba_unmultiplied` is not a `const fn` so translucent colors are
+/// premultiplied here
+fn egui_color(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == 255 {
+        return format!("Color32::from_rgb({r}, {g}, {b})");
+    }
+
+    let premultiply = |channel: u8| (channel as u16 * a as u16 + 127) / 255;
+    format!(
+        "Color32::from_rgba_premultiplied({}, {}, {}, {a})",
+        premultiply(r),
+        premultiply(g),
+        premultiply(b)
+    )
+}
+
+const SLINT_MAPPING: [&str; 3] = [
+    "<name>                  <- palette.<name>",
+    "<role>-<strength>       <- extended.<role>.<strength>.color",
+    "<role>-<strength>-text  <- extended.<role>.<strength>.text",
+];
+
+fn to_slint(document: &ThemeDocument) -> String {
+    let mut slint = header(document, "//", &SLINT_MAPPING);
+    slint.push_str("export global Palette {\n");
+    slint.push_str(&format!(
+        "    out property <bool> dark: {};\n",
+        document.is_dark
+    ));
+    for select in Select::all() {
+        slint.push_str(&format!(
+            "    out property <color> {}: {};\n",
+            slot_words(select).join("-"),
+            color::to_hex(document.color(select))
+        ));
+    }
+    slint.push_str("}\n");
+    slint
+}
+
+const RATATUI_MAPPING: [&str; 4] = [
+    "palette::<NAME>             <- palette.<name>, alpha is dropped",
+    "palette::TEXT_STYLE         <- fg palette.text, bg palette.background",
+    "extended::<ROLE>_<STRENGTH> <- fg extended.<role>.<strength>.text",
+    "                               bg extended.<role>.<strength>.color",
+];
+
+fn to_ratatui(document: &ThemeDocument) -> String {
+    let rgb = |color: Color| {
+        let [r, g, b, _] = color.into_rgba8();
+        format!("Color::Rgb({r}, {g}, {b})")
+    };
+
+    let mut rust = header(document, "//", &RATATUI_MAPPING);
+    rust.push_str(
+        "use ratatui::style::{Color, Style};\n\npub mod palette {\n    use super::*;\n\n",
+    );
+    for (index, color) in document.palette.iter().enumerate() {
+        rust.push_str(&format!(
+            "    pub const {}: Color = {};\n",
+            const_name(Select::Palette(index)),
+            rgb(*color)
+        ));
+    }
+    rust.push_str("\n    pub const TEXT_STYLE: Style = Style::new().fg(TEXT).bg(BACKGROUND);\n}\n");
+
+    rust.push_str("\npub mod extended {\n    use super::*;\n\n");
+    for (index, pair) in document.extended.iter().enumerate() {
+        rust.push_str(&format!(
+            "    pub const {}: Style = Style::new().fg({}).bg({});\n",
+            const_name(Select::Extended((index, 0))),
+            rgb(pair[1]),
+            rgb(pair[0])
+        ));
+    }
+    rust.push_str("}\n");
+    rust
+}
+
+const BEVY_MAPPING: [&str; 3] = [
+    "<NAME>                  <- palette.<name>",
+    "<ROLE>_<STRENGTH>       <- extended.<role>.<strength>.color",
+    "<ROLE>_<STRENGTH>_TEXT  <- extended.<role>.<strength>.text",
+];
+
+fn to_bevy(document: &ThemeDocument) -> String {
+    let mut rust = header(document, "//", &BEVY_MAPPING);
+    rust.push_str("use bevy::color::Color;\n\n");
+    for select in Select::all() {
+        let color = document.color(select);
+        rust.push_str(&format!(
+            "pub const {}: Color = Color::srgba({:.4}, {:.4}, {:.4}, {:.4});\n",
+            const_name(select),
+            color.r,
+            color.g,
+            color.b,
+            color.a
+        ));
+    }
+    rust
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+
+    use super::*;
+
+    fn document() -> ThemeDocument {
+        let mut document = ThemeDocument::from_theme(&Theme::Dark);
+        document.palette[1] = Color::from_rgb8(0x12, 0x34, 0x56);
+        document.extended[4][1] = Color::from_rgba8(200, 100, 50, 0.5);
+        document
+    }
+
+    fn assert_lines(output: &str, lines: &[&str]) {
+        for line in lines {
+            assert!(
+                output.lines().any(|output| output == *line),
+                "{line:?} missing from\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn egui_colors_are_const() {
+        let output = FrameworkFormat::Egui.export(&document());
+        syn::parse_file(&output).unwrap();
+        assert_lines(
+            &output,
+            &[
+                "pub const PRIMARY: Color32 = Color32::from_rgb(18, 52, 86);",
+                "pub const PRIMARY_WEAK_TEXT: Color32 = Color32::from_rgba_premultiplied(100, 50, 25, 128);",
+                "    let mut visuals = Visuals::dark();",
+            ],
+        );
+        assert!(!output.contains("unmultiplied"));
+    }
+
+    #[test]
+    fn slint() {
+        let output = FrameworkFormat::Slint.export(&document());
+        assert_lines(
+            &output,
+            &[
+                "export global Palette {",
+                "    out property <bool> dark: true;",
+                "    out property <color> primary: #123456;",
+                "    out property <color> primary-weak-text: #c8643280;",
+                "}",
+            ],
+        );
+    }
+
+    #[test]
+    fn ratatui() {
+        let output = FrameworkFormat::Ratatui.export(&document());
+        syn::parse_file(&output).unwrap();
+        assert_lines(
+            &output,
+            &[
+                "    pub const PRIMARY: Color = Color::Rgb(18, 52, 86);",
+                "    pub const PRIMARY_WEAK: Style = Style::new().fg(Color::Rgb(200, 100, 50)).bg(Color::Rgb(76, 100, 181));",
+            ],
+        );
+    }
+
+    #[test]
+    fn bevy() {
+        let output = FrameworkFormat::Bevy.export(&document());
+        syn::parse_file(&output).unwrap();
+        assert_lines(
+            &output,
+            &[
+                "pub const PRIMARY: Color = Color::srgba(0.0706, 0.2039, 0.3373, 1.0000);",
+                "pub const PRIMARY_WEAK_TEXT: Color = Color::srgba(0.7843, 0.3922, 0.1961, 0.5000);",
+            ],
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use iced::Color;
+
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    pub palette: [Color; 5],
+    pub extended: [[Color; 2]; 15],
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+impl History {
+    pub fn from_stacks(undo: Vec<Snapshot>, redo: Vec<Snapshot>) -> Self {
+        Self { undo, redo }
+    }
+
+    pub fn undo_stack(&self) -> &[Snapshot] {
+        &self.undo
+    }
+
+    pub fn redo_stack(&self) -> &[Snapshot] {
+        &self.redo
+    }
+
+    pub fn record(&mut self, snapshot: Snapshot) {
+        if self.undo.last() == Some(&snapshot) {
+            return;
+        }
+        if self.undo.len() == MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.undo.push(snapshot);
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
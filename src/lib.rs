@@ -0,0 +1,13 @@
+//! Reading the theme files written by the Theme Colors editor from other iced apps, see [`runtime`]
+//! for loading them at startup, [`build`] for compiling them in and [`live_client`] for following
+//! the editor while it runs.
+
+pub mod arrays;
+pub mod build;
+pub mod color;
+pub mod document;
+pub mod live_client;
+pub mod runtime;
+mod select;
+
+pub use select::{Select, PAIR_NAMES, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES};
@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde_json::json;
+
+use crate::{color, document::ThemeDocument};
+
+/// One theme per line as JSON, palette in `PALETTE_NAMES` order and Extended as
+/// `[color, text]` pairs, role by role from base to strong
+pub fn encode(document: &ThemeDocument) -> String {
+    let line = json!({
+        "name": document.name,
+        "is_dark": document.is_dark,
+        "palette": document.palette.map(color::to_hex),
+        "extended": document.extended.map(|pair| pair.map(color::to_hex)),
+    });
+    format!("{line}\n")
+}
+
+/// Pushes every theme it is given to all connected clients, new clients get the latest theme
+/// right away. Any program reading lines from the socket can be a client, `socat - UNIX-CONNECT:<path>`
+/// works for a quick look.
+pub struct LiveServer {
+    pub path: PathBuf,
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    latest: String,
+    clients: Vec<Box<dyn Write + Send>>,
+    stopped: bool,
+}
+
+impl LiveServer {
+    #[cfg(unix)]
+    pub fn start(path: PathBuf) -> io::Result<Self> {
+        use std::os::unix::net::UnixListener;
+
+        // A socket left behind by an editor that did not exit cleanly would make bind fail
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+
+        let accepting = Arc::clone(&shared);
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut shared = accepting.lock().expect("live server lock");
+                if shared.stopped {
+                    break;
+                }
+                // Writes never wait for a client, one that stops reading is dropped as soon as
+                // its socket buffer is full instead of stalling the editor
+                if stream.set_nonblocking(true).is_ok()
+                    && stream.write_all(shared.latest.as_bytes()).is_ok()
+                {
+                    shared.clients.push(Box::new(stream));
+                }
+            }
+        });
+
+        Ok(Self { path, shared })
+    }
+
+    #[cfg(not(unix))]
+    pub fn start(_path: PathBuf) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "live push needs Unix domain sockets",
+        ))
+    }
+
+    /// Sends the line unless it is the one that was sent last, clients that went away or fell
+    /// behind are dropped
+    pub fn broadcast(&self, line: String) {
+        let mut shared = self.shared.lock().expect("live server lock");
+        if shared.latest == line {
+            return;
+        }
+
+        shared
+            .clients
+            .retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+        shared.latest = line;
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.shared.lock().expect("live server lock").clients.len()
+    }
+}
+
+impl Drop for LiveServer {
+    /// Disconnects every client and wakes the accepting thread so it can finish
+    fn drop(&mut self) {
+        {
+            let mut shared = self.shared.lock().expect("live server lock");
+            shared.stopped = true;
+            shared.clients.clear();
+        }
+
+        #[cfg(unix)]
+        let _ = std::os::unix::net::UnixStream::connect(&self.path);
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+    use theme_colors::live_client::LiveTheme;
+
+    use super::*;
+
+    /// The protocol sends 8-bit colors, so the document is rounded the same way first
+    fn document(theme: &Theme) -> ThemeDocument {
+        ThemeDocument::from_toml(&ThemeDocument::from_theme(theme).to_toml()).unwrap()
+    }
+
+    #[test]
+    fn clients_read_what_is_encoded() {
+        let document = document(&Theme::GruvboxLight);
+        let line = encode(&document);
+        assert!(line.ends_with('\n') && line.matches('\n').count() == 1);
+
+        let live = LiveTheme::parse(&line).unwrap();
+        assert_eq!(live.name, document.name);
+        assert_eq!(live.is_dark, document.is_dark);
+        assert_eq!(live.palette, document.palette);
+        assert_eq!(live.extended, document.extended);
+        assert_eq!(live.to_theme(), document.to_theme());
+    }
+
+    #[test]
+    fn clients_ignore_lines_they_cannot_read() {
+        let line = encode(&document(&Theme::Dark));
+        assert_eq!(LiveTheme::parse("not json"), None);
+        assert_eq!(LiveTheme::parse(&line.replace("#", "#zz")), None);
+        assert_eq!(
+            LiveTheme::parse(&line.replace("\"is_dark\"", "\"dark\"")),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    mod socket {
+        use std::{
+            io::{BufRead, BufReader},
+            os::unix::net::UnixStream,
+            time::{Duration, Instant},
+        };
+
+        use super::*;
+
+        fn socket_path(name: &str) -> PathBuf {
+            std::env::temp_dir().join(format!(
+                "theme_colors-test-{}-{}.sock",
+                std::process::id(),
+                name
+            ))
+        }
+
+        fn wait_for_clients(server: &LiveServer, count: usize) {
+            let start = Instant::now();
+            while server.client_count() != count {
+                assert!(start.elapsed() < Duration::from_secs(5), "no client");
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        #[test]
+        fn pushes_themes_to_clients() {
+            let server = LiveServer::start(socket_path("push")).unwrap();
+            let first = document(&Theme::Nord);
+            server.broadcast(encode(&first));
+
+            let stream = UnixStream::connect(&server.path).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let mut lines = BufReader::new(stream).lines();
+            let read = |lines: &mut std::io::Lines<_>| {
+                LiveTheme::parse(&lines.next().unwrap().unwrap()).unwrap()
+            };
+
+            assert_eq!(read(&mut lines).palette, first.palette);
+
+            let second = document(&Theme::SolarizedLight);
+            server.broadcast(encode(&second));
+            server.broadcast(encode(&second));
+            server.broadcast(encode(&first));
+            assert_eq!(read(&mut lines).palette, second.palette);
+            assert_eq!(read(&mut lines).palette, first.palette);
+
+            let path = server.path.clone();
+            drop(server);
+            assert!(lines.next().is_none());
+            assert!(!path.exists());
+        }
+
+        #[test]
+        fn stalled_clients_are_dropped() {
+            let server = LiveServer::start(socket_path("stalled")).unwrap();
+            let _stalled = UnixStream::connect(&server.path).unwrap();
+            wait_for_clients(&server, 1);
+
+            let start = Instant::now();
+            let mut document = document(&Theme::Dark);
+            for index in 0..10_000 {
+                document.name = format!("Dark {index}");
+                server.broadcast(encode(&document));
+                if server.client_count() == 0 {
+                    break;
+                }
+            }
+
+            assert_eq!(server.client_count(), 0);
+            assert!(start.elapsed() < Duration::from_secs(1));
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Follows the themes pushed by the Theme Colors editor over its local socket.
+//!
+//! Subscribe to [`subscription`] and replace the app theme with [`LiveTheme::to_theme`] on every
+//! update.
+
+use std::{
+    env,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    Color, Subscription, Theme,
+};
+use serde_json::Value;
+
+use crate::{arrays, color};
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveTheme {
+    pub name: String,
+    pub is_dark: bool,
+    pub palette: [Color; 5],
+    pub extended: [[Color; 2]; 15],
+}
+
+/// `$XDG_RUNTIME_DIR/theme_colors.sock`, or the same name in the temp directory
+pub fn default_socket_path() -> PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+        .join("theme_colors.sock")
+}
+
+impl LiveTheme {
+    /// Reads one line of the editor's JSON protocol
+    pub fn parse(line: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(line).ok()?;
+        let color = |value: &Value| color::from_hex(value.as_str()?);
+
+        let palette: Vec<Color> = value["palette"]
+            .as_array()?
+            .iter()
+            .map(color)
+            .collect::<Option<_>>()?;
+        let extended: Vec<[Color; 2]> = value["extended"]
+            .as_array()?
+            .iter()
+            .map(|pair| Some([color(&pair[0])?, color(&pair[1])?]))
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            name: value["name"].as_str().unwrap_or("Live").to_string(),
+            is_dark: value["is_dark"].as_bool()?,
+            palette: palette.try_into().ok()?,
+            extended: extended.try_into().ok()?,
+        })
+    }
+
+    pub fn to_theme(&self) -> Theme {
+        let palette = arrays::palette_from_array(&self.palette);
+        let extended = arrays::extended_from_array(&self.extended, self.is_dark);
+
+        Theme::custom_with_fn(self.name.clone(), palette, |_| extended)
+    }
+}
+
+/// Emits a theme every time the editor pushes one, reconnecting whenever the editor is not running
+#[cfg(unix)]
+pub fn subscription(path: PathBuf) -> Subscription<LiveTheme> {
+    iced::subscription::channel(path.clone(), 16, |mut output| async move {
+        let (sender, mut receiver) = mpsc::unbounded();
+
+        // Ends once the subscription is dropped, which closes the channel
+        thread::spawn(move || {
+            while !sender.is_closed() {
+                if let Ok(stream) = std::os::unix::net::UnixStream::connect(&path) {
+                    follow(stream, &sender);
+                }
+                thread::sleep(RECONNECT_INTERVAL);
+            }
+        });
+
+        loop {
+            match receiver.next().await {
+                Some(theme) => {
+                    let _ = output.send(theme).await;
+                }
+                None => iced::futures::future::pending::<()>().await,
+            }
+        }
+    })
+}
+
+/// Forwards every theme on the stream until the editor disconnects or the channel is closed, the
+/// read timeout wakes the thread up to check the channel while the editor is quiet
+#[cfg(unix)]
+fn follow(stream: std::os::unix::net::UnixStream, sender: &mpsc::UnboundedSender<LiveTheme>) {
+    if stream.set_read_timeout(Some(RECONNECT_INTERVAL)).is_err() {
+        return;
+    }
+    let mut reader = BufReader::new(stream);
+    // Bytes, so a line cut by a timeout is completed by the next read
+    let mut line = Vec::new();
+
+    while !sender.is_closed() {
+        match reader.read_until(b'\n', &mut line) {
+            Ok(_) if line.ends_with(b"\n") => {
+                if let Some(theme) = LiveTheme::parse(&String::from_utf8_lossy(&line)) {
+                    let _ = sender.unbounded_send(theme);
+                }
+                line.clear();
+            }
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            _ => return,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn subscription(_path: PathBuf) -> Subscription<LiveTheme> {
+    Subscription::none()
+}
@@ -2,25 +2,92 @@
 
 use iced::{
     border::Radius,
-    theme::{
-        self,
-        palette::{Danger, Extended, Pair, Primary, Secondary, Success},
-        Palette,
-    },
+    keyboard::Modifiers,
+    theme::{self, palette::Extended, Palette},
     widget::{self, button, column, container, row, text, text_input, tooltip::Position, Column},
-    Application, Background, Border, Color, Command, Length, Settings, Size, Theme,
+    Application, Background, Border, Color, Command, Event, Length, Settings, Size, Subscription,
+    Theme,
 };
 
+use theme_colors::{
+    arrays,
+    color::{self, Gamut, Hsl, Oklch},
+    document,
+    document::ThemeDocument,
+    live_client, runtime, Select, PAIR_NAMES, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES,
+};
+
+use blend::BlendSpace;
+use contrast::ContrastMethod;
+use frameworks::FrameworkFormat;
+use history::{History, Snapshot};
+use live::LiveServer;
+use preferences::Preferences;
+use scale::ScaleFormat;
+use session::Session;
+use shortcuts::Direction;
+use split::{Side, SourceMessage, SplitSide};
+use swatch_sheet::SheetFormat;
+use terminal::{ImportMapping, ImportMessage};
+use tokens::TokenFormat;
+use transform::Transform;
+use watch::WatchedFile;
+
+mod blend;
+mod cli;
+mod compare;
+mod contrast;
+mod exchange;
+mod frameworks;
+mod history;
+mod live;
+mod paths;
+mod preferences;
+mod rust_source;
+mod scale;
+mod session;
+mod share;
+mod shortcuts;
+mod split;
+mod style_guide;
+mod swatch_sheet;
+mod terminal;
+mod tokens;
+mod transform;
+mod watch;
+
 fn main() {
-    let mut settings = Settings::default();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(result) = cli::run(&args) {
+        match result {
+            Ok(message) => println!("{message}"),
+            Err(error) => {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let preferences = Preferences::load();
+    let window = preferences.window;
+
+    let mut settings = Settings::with_flags(preferences);
+    settings.window.exit_on_close_request = false;
     settings.window.min_size = Some(Size {
         width: 900.,
         height: 880.,
     });
+    settings.window.size = Size::new(window.width, window.height);
+    if let (Some(x), Some(y)) = (window.x, window.y) {
+        settings.window.position = iced::window::Position::Specific(iced::Point::new(x, y));
+    }
 
     ThemeColors::run(settings).unwrap()
 }
 
+const AUTOSAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
 #[derive(Debug, Clone)]
 pub enum Message {
     None,
@@ -29,20 +96,197 @@ pub enum Message {
     GenerateFromBase,
     SelectWorkingTheme(Theme),
     SelectAppTheme(Theme),
-    SelectColor(Select),
+    SwatchPressed(Select),
+    SwatchReleased(Select),
+    DragEnded,
+    CopySelected,
+    PasteSelected,
+    Paste(Option<String>),
+    SwapColorAndText,
+    SelectRole(usize),
+    SelectStrength(usize),
+    ModifiersChanged(Modifiers),
+    SelectAdjustMode(AdjustMode),
+    Navigate(Direction),
+    ToggleSelectedPair,
+    SelectChannel(Channel),
+    NudgeSelected(f32),
     AdjustRed(f32),
     AdjustGreen(f32),
     AdjustBlue(f32),
     AdjustAlpha(f32),
+    AdjustOkLightness(f32),
+    AdjustOkChroma(f32),
+    AdjustOkHue(f32),
+    SelectGamut(Gamut),
+    SelectContrastMethod(ContrastMethod),
+    ToggleContrastRole(usize, bool),
+    HoverContrastCell(Select, Select),
+    EditColor(Select),
+    FitSelectionToGamut,
+    Undo,
+    Redo,
+    ToggleLockSelected,
+    SelectTool(Tool),
+    SelectPage(Page),
+    RevertSlot(Select),
+    ToggleChangedOnly(bool),
+    FilePathChanged(String),
+    OpenFile,
+    SaveFile,
+    SplitSource(Side, SourceMessage),
+    TakeSlot(Side, Select),
+    BlendSource(Side, SourceMessage),
+    BlendRatio(f32),
+    SelectBlendSpace(BlendSpace),
+    ApplyBlend,
+    BlendStepsChanged(String),
+    BlendDirectoryChanged(String),
+    ExportBlendSequence,
+    PickScaleColor(Color),
+    ExportDirectoryChanged(String),
+    ExportScales(ScaleFormat),
+    ExportTokens(TokenFormat),
+    ExportFramework(FrameworkFormat),
+    ExportSwatchSheet(SheetFormat),
+    ExportStyleGuide,
+    ImportPathChanged(String),
+    ImportTokens,
+    ImportScheme,
+    CopyShareCode,
+    ShareCodeChanged(String),
+    ImportShareCode,
+    ImportRustSource,
+    MapImport(ImportMessage),
+    ApplyImport,
+    CancelImport,
+    TransformHue(f32),
+    TransformChroma(f32),
+    TransformLightness(f32),
+    TransformTemperature(f32),
+    ApplyTransform,
+    CancelTransform,
+    Autosave,
+    RestoreSession,
+    DiscardSession,
+    CloseRequested,
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    OpenRecent(String),
+    CheckWatchedFile,
+    ReloadFromDisk,
+    KeepEdits,
+    ToggleLiveServer(bool),
     // ToggleThemeSelection,
     // ToggleLightDarkTheme,
     TryTheme,
 }
 
-#[derive(Debug, Clone)]
-pub enum Select {
-    Palette(usize),
-    Extended((usize, usize)),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    Hue,
+    Saturation,
+    Lightness,
+    OkLightness,
+    OkChroma,
+    OkHue,
+}
+
+/// OKLCH chroma at the end of the slider, a little above the most saturated Display P3 colors
+const MAX_OK_CHROMA: f32 = 0.4;
+
+impl Channel {
+    /// All channels are normalized to `0.0..=1.0`, hue included
+    fn get(self, color: Color) -> f32 {
+        match self {
+            Channel::Red => color.r,
+            Channel::Green => color.g,
+            Channel::Blue => color.b,
+            Channel::Alpha => color.a,
+            Channel::Hue => Hsl::from_color(color).h / 360.0,
+            Channel::Saturation => Hsl::from_color(color).s,
+            Channel::Lightness => Hsl::from_color(color).l,
+            Channel::OkLightness => Oklch::from_color(color).l,
+            Channel::OkChroma => Oklch::from_color(color).c / MAX_OK_CHROMA,
+            Channel::OkHue => Oklch::from_color(color).h / 360.0,
+        }
+    }
+
+    fn set(self, color: &mut Color, value: f32) {
+        match self {
+            Channel::Red => color.r = value,
+            Channel::Green => color.g = value,
+            Channel::Blue => color.b = value,
+            Channel::Alpha => color.a = value,
+            Channel::Hue | Channel::Saturation | Channel::Lightness => {
+                let mut hsl = Hsl::from_color(*color);
+                match self {
+                    Channel::Hue => hsl.h = value * 360.0,
+                    Channel::Saturation => hsl.s = value,
+                    _ => hsl.l = value,
+                }
+                *color = color::clamp_to_srgb(hsl.to_color(color.a));
+            }
+            Channel::OkLightness | Channel::OkChroma | Channel::OkHue => {
+                let mut lch = Oklch::from_color(*color);
+                match self {
+                    Channel::OkLightness => lch.l = value,
+                    Channel::OkChroma => lch.c = value * MAX_OK_CHROMA,
+                    _ => lch.h = value * 360.0,
+                }
+                // Not clamped, colors outside of the gamut are marked and can be fitted by chroma
+                *color = lch.to_color(color.a);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Channel::Red => "Red",
+            Channel::Green => "Green",
+            Channel::Blue => "Blue",
+            Channel::Alpha => "Alpha",
+            Channel::Hue => "Hue",
+            Channel::Saturation => "Saturation",
+            Channel::Lightness => "Lightness",
+            Channel::OkLightness => "OK Lightness",
+            Channel::OkChroma => "OK Chroma",
+            Channel::OkHue => "OK Hue",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a slider change is applied to the other colors in a multi-selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AdjustMode {
+    /// Every selected color gets the slider value
+    Absolute,
+    /// Every selected color is moved by the same amount as the primary selection
+    Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Adjust,
+    Transform,
+    Blend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    Editor,
+    Compare,
+    Split,
+    Scales,
+    Contrast,
+    Exchange,
 }
 
 pub struct ThemeColors {
@@ -51,27 +295,124 @@ pub struct ThemeColors {
     working_theme: Theme,
     palette: [Color; 5],
     extended: [[Color; 2]; 15],
+    palette_locked: [bool; 5],
+    extended_locked: [[bool; 2]; 15],
     selected: Select,
+    selection: Vec<Select>,
+    dragging: Option<Select>,
+    copied: Option<Color>,
+    modifiers: Modifiers,
+    adjust_mode: AdjustMode,
+    gamut: Gamut,
+    contrast_method: ContrastMethod,
+    contrast_roles: [bool; 6],
+    contrast_hover: Option<(Select, Select)>,
+    channel: Channel,
+    page: Page,
+    compare_changed_only: bool,
+    split: [SplitSide; 2],
+    blend_sources: [SplitSide; 2],
+    blend_ratio: f32,
+    blend_space: BlendSpace,
+    blend_steps: String,
+    blend_directory: String,
+    file_path: String,
+    export_directory: String,
+    import_path: String,
+    scheme_import: Option<ImportMapping>,
+    import_report: Vec<String>,
+    share_code: String,
+    pending_session: Option<Session>,
+    last_autosave: String,
+    /// Window geometry and recent files, the other preferences are read from the editor on save
+    preferences: Preferences,
+    last_saved_preferences: Preferences,
+    watched_file: Option<WatchedFile>,
+    /// The opened file changed on disk while there were unsaved edits
+    reload_conflict: Option<ThemeDocument>,
+    live_server: Option<LiveServer>,
+    status: Option<String>,
+    tool: Tool,
+    transform: Transform,
+    history: History,
+    editing_selected: bool,
 }
 
 impl Application for ThemeColors {
     type Executor = iced::executor::Default;
     type Message = Message;
     type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        let working_theme = iced::Theme::Dark;
-        let extended = Self::populate_extended_array(working_theme.extended_palette());
-        let palette = Self::populate_palette_array(working_theme.palette());
-        let colorpicker = Self {
-            themes: Self::themes_array(),
-            app_theme: Theme::Dark,
+    type Flags = Preferences;
+
+    fn new(preferences: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let working_theme = preferences.working_theme().unwrap_or(Theme::Dark);
+        let mut themes = Self::themes_array();
+        if !Theme::ALL.contains(&working_theme) {
+            themes[0] = working_theme.clone();
+        }
+        let extended = arrays::populate_extended_array(working_theme.extended_palette());
+        let palette = arrays::populate_palette_array(working_theme.palette());
+        let (pending_session, status) = match Session::load() {
+            Some(Ok(session)) => (Some(session), None),
+            Some(Err(error)) => (
+                None,
+                Some(format!("Could not read the previous session: {}", error)),
+            ),
+            None => (None, None),
+        };
+        let mut colorpicker = Self {
+            themes,
+            app_theme: preferences.app_theme().unwrap_or(Theme::Dark),
             working_theme,
             palette,
             extended,
+            palette_locked: [false; 5],
+            extended_locked: [[false; 2]; 15],
             selected: Select::Palette(0),
+            selection: vec![Select::Palette(0)],
+            dragging: None,
+            copied: None,
+            modifiers: Modifiers::default(),
+            adjust_mode: preferences.adjust_mode,
+            gamut: preferences.gamut,
+            contrast_method: preferences.contrast_method,
+            contrast_roles: [true; 6],
+            contrast_hover: None,
+            channel: Channel::Red,
+            page: Page::Editor,
+            compare_changed_only: false,
+            split: [
+                SplitSide::new(Theme::TokyoNight),
+                SplitSide::new(Theme::Nightfly),
+            ],
+            blend_sources: [SplitSide::new(Theme::Dracula), SplitSide::new(Theme::Nord)],
+            blend_ratio: 0.5,
+            blend_space: preferences.color_space,
+            blend_steps: preferences.blend_steps.clone(),
+            blend_directory: preferences.blend_directory.clone(),
+            file_path: String::new(),
+            export_directory: preferences.export_directory.clone(),
+            import_path: String::new(),
+            scheme_import: None,
+            import_report: Vec::new(),
+            share_code: String::new(),
+            pending_session,
+            last_autosave: String::new(),
+            last_saved_preferences: preferences.clone(),
+            watched_file: None,
+            reload_conflict: None,
+            live_server: None,
+            preferences,
+            status,
+            tool: Tool::Adjust,
+            transform: Transform::default(),
+            history: History::default(),
+            editing_selected: false,
         };
+        if colorpicker.pending_session.is_some() {
+            // The previous session stays on disk until there is something new to save
+            colorpicker.last_autosave = colorpicker.session().to_json();
+        }
 
         (colorpicker, iced::Command::none())
     }
@@ -79,33 +420,446 @@ impl Application for ThemeColors {
     fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
         match message {
             Message::None => {}
-            Message::ResetSelected => match self.selected {
-                Select::Palette(index) => {
-                    let palette = Self::populate_palette_array(self.working_theme.palette());
-                    self.palette[index] = palette[index];
+            Message::ResetSelected => {
+                self.record_history();
+                for select in self.selection.clone() {
+                    *self.color_mut(select) = self.working_color(select);
                 }
-                Select::Extended((index1, index2)) => {
-                    let extended =
-                        Self::populate_extended_array(self.working_theme.extended_palette());
-                    self.extended[index1][index2] = extended[index1][index2];
-                }
-            },
+            }
             Message::ResetAll => {
-                self.palette = Self::populate_palette_array(self.working_theme.palette());
-                self.extended = Self::populate_extended_array(self.working_theme.extended_palette())
+                self.record_history();
+                self.palette = arrays::populate_palette_array(self.working_theme.palette());
+                self.extended =
+                    arrays::populate_extended_array(self.working_theme.extended_palette())
+            }
+            Message::GenerateFromBase => {
+                self.record_history();
+                self.generate_extended_from_palette()
             }
-            Message::GenerateFromBase => self.generate_extended_from_palette(),
             Message::SelectAppTheme(theme) => self.app_theme = theme,
             Message::SelectWorkingTheme(theme) => {
-                self.palette = Self::populate_palette_array(theme.palette());
-                self.extended = Self::populate_extended_array(theme.extended_palette());
+                self.record_history();
+                self.palette = arrays::populate_palette_array(theme.palette());
+                self.extended = arrays::populate_extended_array(theme.extended_palette());
                 self.working_theme = theme;
             }
-            Message::SelectColor(selected) => self.selected = selected,
-            Message::AdjustRed(new_value) => self.adjust_selected_red_color(new_value),
-            Message::AdjustGreen(new_value) => self.adjust_selected_green_color(new_value),
-            Message::AdjustBlue(new_value) => self.adjust_selected_blue_color(new_value),
-            Message::AdjustAlpha(new_value) => self.adjust_selected_alpha_color(new_value),
+            Message::SwatchPressed(select) => self.dragging = Some(select),
+            Message::SwatchReleased(target) => match self.dragging.take() {
+                Some(source) if source != target => self.drop_color(source, target),
+                _ => self.select_color(target),
+            },
+            Message::DragEnded => self.dragging = None,
+            Message::CopySelected => {
+                let color = self.color(self.selected);
+                self.copied = Some(color);
+                return iced::clipboard::write(color::to_hex(color));
+            }
+            Message::PasteSelected => return iced::clipboard::read(Message::Paste),
+            Message::Paste(contents) => {
+                let pasted = contents
+                    .as_deref()
+                    .and_then(color::from_hex)
+                    .or(self.copied);
+
+                if let Some(color) = pasted {
+                    self.record_history();
+                    for select in self.selection.clone() {
+                        *self.color_mut(select) = color;
+                    }
+                }
+            }
+            Message::SwapColorAndText => {
+                self.record_history();
+                let mut swapped = Vec::new();
+                for select in &self.selection {
+                    if let Select::Extended((index, _)) = select {
+                        if !swapped.contains(index) {
+                            self.extended[*index].swap(0, 1);
+                            swapped.push(*index);
+                        }
+                    }
+                }
+            }
+            Message::SelectRole(role) => {
+                let slots = (role * 3..role * 3 + 3).map(|index| Select::Extended((index, 0)));
+                self.select_group(slots.collect());
+            }
+            Message::SelectStrength(strength) => {
+                let slots = (0..5).map(|role| Select::Extended((role * 3 + strength, 0)));
+                self.select_group(slots.collect());
+            }
+            Message::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+            Message::SelectAdjustMode(mode) => self.adjust_mode = mode,
+            Message::Navigate(direction) => self.navigate(direction),
+            Message::ToggleSelectedPair => {
+                if let Select::Extended((index, pair)) = self.selected {
+                    self.select_single(Select::Extended((index, 1 - pair)));
+                }
+            }
+            Message::SelectChannel(channel) => self.channel = channel,
+            Message::NudgeSelected(step) => {
+                self.record_selected_edit();
+                let mut new_value = self.channel.get(self.color(self.selected)) + step;
+                if matches!(self.channel, Channel::Hue | Channel::OkHue) {
+                    new_value = new_value.rem_euclid(1.0);
+                }
+                self.adjust_selected_color(self.channel, new_value)
+            }
+            Message::AdjustRed(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::Red, new_value)
+            }
+            Message::AdjustGreen(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::Green, new_value)
+            }
+            Message::AdjustBlue(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::Blue, new_value)
+            }
+            Message::AdjustAlpha(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::Alpha, new_value)
+            }
+            Message::AdjustOkLightness(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::OkLightness, new_value)
+            }
+            Message::AdjustOkChroma(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::OkChroma, new_value)
+            }
+            Message::AdjustOkHue(new_value) => {
+                self.record_selected_edit();
+                self.adjust_selected_color(Channel::OkHue, new_value)
+            }
+            Message::SelectGamut(gamut) => self.gamut = gamut,
+            Message::SelectContrastMethod(method) => self.contrast_method = method,
+            Message::ToggleContrastRole(role, shown) => self.contrast_roles[role] = shown,
+            Message::HoverContrastCell(text, background) => {
+                self.contrast_hover = Some((text, background))
+            }
+            Message::EditColor(select) => {
+                self.page = Page::Editor;
+                self.select_single(select);
+            }
+            Message::FitSelectionToGamut => {
+                self.record_history();
+                for select in self.selection.clone() {
+                    let gamut = self.gamut;
+                    let color = self.color_mut(select);
+                    *color = gamut.map(*color);
+                }
+            }
+            Message::Undo => {
+                if let Some(snapshot) = self.history.undo(self.snapshot()) {
+                    self.restore(snapshot);
+                }
+            }
+            Message::Redo => {
+                if let Some(snapshot) = self.history.redo(self.snapshot()) {
+                    self.restore(snapshot);
+                }
+            }
+            Message::ToggleLockSelected => {
+                let locked = !self.is_selected_locked();
+                for select in self.selection.clone() {
+                    match select {
+                        Select::Palette(index) => self.palette_locked[index] = locked,
+                        Select::Extended((index1, index2)) => {
+                            self.extended_locked[index1][index2] = locked
+                        }
+                    }
+                }
+            }
+            Message::SelectTool(tool) => self.tool = tool,
+            Message::SelectPage(page) => self.page = page,
+            Message::RevertSlot(select) => {
+                self.record_history();
+                *self.color_mut(select) = self.working_color(select);
+            }
+            Message::ToggleChangedOnly(changed_only) => self.compare_changed_only = changed_only,
+            Message::FilePathChanged(path) => self.file_path = path,
+            Message::OpenFile => match ThemeDocument::load(&self.file_path) {
+                Ok(document) => {
+                    self.record_history();
+                    self.load_document(document);
+                    self.watched_file = Some(WatchedFile::new(&self.file_path, self.snapshot()));
+                    self.preferences.add_recent_file(&self.file_path);
+                    self.status = Some(format!("Opened {}", self.file_path));
+                }
+                Err(error) => self.status = Some(format!("Could not open theme: {}", error)),
+            },
+            Message::SaveFile => match self.document().save(&self.file_path) {
+                Ok(()) => {
+                    self.watched_file = Some(WatchedFile::new(&self.file_path, self.snapshot()));
+                    self.preferences.add_recent_file(&self.file_path);
+                    let clamped = Select::all()
+                        .filter(|select| !Gamut::Srgb.contains(self.color(*select)))
+                        .count();
+                    self.status = Some(if clamped == 0 {
+                        format!("Saved {}", self.file_path)
+                    } else {
+                        format!(
+                            "Saved {}, {} colors outside of sRGB were clamped",
+                            self.file_path, clamped
+                        )
+                    });
+                }
+                Err(error) => self.status = Some(format!("Could not save theme: {}", error)),
+            },
+            Message::SplitSource(side, message) => self.split[side as usize].update(message),
+            Message::TakeSlot(side, select) => {
+                self.record_history();
+                *self.color_mut(select) = self.split[side as usize].document.color(select);
+            }
+            Message::BlendSource(side, message) => {
+                self.blend_sources[side as usize].update(message)
+            }
+            Message::BlendRatio(ratio) => self.blend_ratio = ratio,
+            Message::SelectBlendSpace(space) => self.blend_space = space,
+            Message::ApplyBlend => {
+                let [from, to] = &self.blend_sources;
+                let blended = blend::blend(
+                    &from.document,
+                    &to.document,
+                    self.blend_ratio,
+                    self.blend_space,
+                );
+                self.record_history();
+                self.palette = blended.palette;
+                self.extended = blended.extended;
+            }
+            Message::PickScaleColor(color) => {
+                self.record_history();
+                for select in self.selection.clone() {
+                    *self.color_mut(select) = color;
+                }
+            }
+            Message::ExportDirectoryChanged(directory) => self.export_directory = directory,
+            Message::ExportScales(format) => {
+                let scales = scale::generate(scale::seeds(&self.palette, &self.extended));
+                self.export(format.file_name(), format.export(&scales));
+            }
+            Message::ExportTokens(format) => {
+                let document = self.document();
+                self.export(
+                    &format.file_name(&document.name),
+                    tokens::export(&document, format),
+                );
+            }
+            Message::ExportFramework(format) => {
+                self.export(format.file_name(), format.export(&self.document()))
+            }
+            Message::ExportSwatchSheet(format) => match format.export(&self.document()) {
+                Ok(contents) => self.export(format.file_name(), contents),
+                Err(error) => self.status = Some(format!("Could not render swatches: {}", error)),
+            },
+            Message::ExportStyleGuide => self.export(
+                style_guide::FILE_NAME,
+                style_guide::to_html(&self.document(), self.gamut),
+            ),
+            Message::ImportPathChanged(path) => self.import_path = path,
+            Message::ImportTokens => {
+                let name = std::path::Path::new(&self.import_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .and_then(|name| name.split('.').next().map(str::to_string))
+                    .unwrap_or_else(|| "Custom".to_string());
+
+                let imported = std::fs::read_to_string(&self.import_path)
+                    .map_err(Into::into)
+                    .and_then(|input| tokens::import(&input, &name));
+
+                match imported {
+                    Ok(document) => {
+                        self.record_history();
+                        self.load_document(document);
+                        self.status = Some(format!("Imported {}", self.import_path));
+                    }
+                    Err(error) => self.status = Some(format!("Could not import tokens: {}", error)),
+                }
+            }
+            Message::ImportScheme => match terminal::load(&self.import_path) {
+                Ok(mapping) => {
+                    self.scheme_import = Some(mapping);
+                    self.status = None;
+                }
+                Err(error) => self.status = Some(format!("Could not import scheme: {}", error)),
+            },
+            Message::ImportRustSource => {
+                let name = std::path::Path::new(&self.import_path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "Custom".to_string());
+
+                let imported = std::fs::read_to_string(&self.import_path)
+                    .map_err(|error| error.to_string())
+                    .and_then(|input| {
+                        rust_source::import(&input, &name, (&self.palette, &self.extended))
+                    });
+
+                match imported {
+                    Ok(import) => {
+                        self.record_history();
+                        self.load_document(import.document);
+                        self.status = Some(match import.unresolved.len() {
+                            0 => format!("Imported {}", self.import_path),
+                            count => format!(
+                                "Imported {} with {} unresolved fields, see Import / Export",
+                                self.import_path, count
+                            ),
+                        });
+                        self.import_report = import.unresolved;
+                    }
+                    Err(error) => {
+                        self.status = Some(format!("Could not import Rust source: {}", error))
+                    }
+                }
+            }
+            Message::CopyShareCode => {
+                self.share_code = share::encode(&self.document());
+                self.status = Some("Copied share code".to_string());
+                return iced::clipboard::write(self.share_code.clone());
+            }
+            Message::ShareCodeChanged(code) => self.share_code = code,
+            Message::ImportShareCode => match share::decode(&self.share_code) {
+                Ok(document) => {
+                    self.record_history();
+                    self.status = Some(format!("Imported share code for {}", document.name));
+                    self.load_document(document);
+                }
+                Err(error) => self.status = Some(error.to_string()),
+            },
+            Message::MapImport(message) => {
+                if let Some(mapping) = &mut self.scheme_import {
+                    mapping.update(message);
+                }
+            }
+            Message::ApplyImport => {
+                if let Some(mapping) = self.scheme_import.take() {
+                    self.record_history();
+                    self.load_document(mapping.document());
+                    self.status = Some(format!("Imported {}", self.import_path));
+                }
+            }
+            Message::CancelImport => self.scheme_import = None,
+            Message::BlendStepsChanged(steps) => self.blend_steps = steps,
+            Message::BlendDirectoryChanged(directory) => self.blend_directory = directory,
+            Message::ExportBlendSequence => {
+                let [from, to] = &self.blend_sources;
+                self.status = match self.blend_steps.trim().parse::<usize>() {
+                    Ok(steps) if steps >= 2 => {
+                        match blend::export_sequence(
+                            &from.document,
+                            &to.document,
+                            steps,
+                            self.blend_space,
+                            std::path::Path::new(&self.blend_directory),
+                            "blend",
+                        ) {
+                            Ok(written) => Some(format!(
+                                "Exported {} themes to {}",
+                                written, self.blend_directory
+                            )),
+                            Err(error) => Some(format!("Could not export blend: {}", error)),
+                        }
+                    }
+                    _ => Some("The number of steps must be at least 2".to_string()),
+                };
+            }
+            Message::TransformHue(new_value) => self.transform.hue = new_value,
+            Message::TransformChroma(new_value) => self.transform.chroma = new_value,
+            Message::TransformLightness(new_value) => self.transform.lightness = new_value,
+            Message::TransformTemperature(new_value) => self.transform.temperature = new_value,
+            Message::ApplyTransform => {
+                if !self.transform.is_identity() {
+                    self.record_history();
+                    let (palette, extended) = self.transformed_colors();
+                    self.palette = palette;
+                    self.extended = extended;
+                    self.transform = Transform::default();
+                }
+            }
+            Message::CancelTransform => self.transform = Transform::default(),
+            Message::Autosave => {
+                self.autosave();
+                self.save_preferences();
+            }
+            Message::WindowResized(width, height) => {
+                self.preferences.window.width = width as f32;
+                self.preferences.window.height = height as f32;
+            }
+            Message::WindowMoved(x, y) => {
+                self.preferences.window.x = Some(x as f32);
+                self.preferences.window.y = Some(y as f32);
+            }
+            Message::CheckWatchedFile => {
+                let current = self.snapshot();
+                let Some(watched) = &mut self.watched_file else {
+                    return Command::none();
+                };
+                if !watched.poll() {
+                    return Command::none();
+                }
+
+                let path = watched.path.display().to_string();
+                let unsaved = watched.saved != current;
+                match ThemeDocument::load(&watched.path) {
+                    Ok(document) if unsaved => self.reload_conflict = Some(document),
+                    Ok(document) => {
+                        self.reload_document(document);
+                        self.status = Some(format!("Reloaded {}", path));
+                    }
+                    Err(error) => {
+                        self.status = Some(format!("Could not reload {}: {}", path, error))
+                    }
+                }
+            }
+            Message::ReloadFromDisk => {
+                if let Some(document) = self.reload_conflict.take() {
+                    self.reload_document(document);
+                    self.status =
+                        Some("Reloaded the theme file, your edits can be undone".to_string());
+                }
+            }
+            Message::KeepEdits => self.reload_conflict = None,
+            Message::ToggleLiveServer(enabled) => {
+                self.live_server = None;
+                if enabled {
+                    let path = live_client::default_socket_path();
+                    match LiveServer::start(path.clone()) {
+                        Ok(server) => {
+                            self.live_server = Some(server);
+                            self.status = Some(format!("Pushing edits to {}", path.display()));
+                        }
+                        Err(error) => {
+                            self.status = Some(format!("Could not start live push: {}", error))
+                        }
+                    }
+                }
+            }
+            Message::OpenRecent(path) => {
+                self.file_path = path;
+                return self.update(Message::OpenFile);
+            }
+            Message::RestoreSession => {
+                if let Some(session) = self.pending_session.take() {
+                    self.restore_session(session);
+                    self.status = Some("Restored the previous session".to_string());
+                }
+            }
+            Message::DiscardSession => {
+                self.pending_session = None;
+                Session::discard();
+                self.last_autosave.clear();
+                self.autosave();
+            }
+            Message::CloseRequested => {
+                self.autosave();
+                self.save_preferences();
+                return iced::window::close(iced::window::Id::MAIN);
+            }
             // Message::ToggleThemeSelection => self.use_selected_theme = !self.use_selected_theme,
             // Message::ToggleLightDarkTheme => self.light_theme = !self.light_theme,
             Message::TryTheme => {
@@ -118,10 +872,16 @@ impl Application for ThemeColors {
                 self.app_theme = self.themes[0].clone();
             }
         }
+
+        if let Some(server) = &self.live_server {
+            server.broadcast(live::encode(&self.document()));
+        }
         Command::none()
     }
 
     fn view(&self) -> iced::Element<'_, Self::Message, Self::Theme, iced::Renderer> {
+        let (palette_colors, extended_colors) = self.transformed_colors();
+
         let label = text("App Theme");
         let app_theme_picker =
             widget::pick_list(self.themes.as_slice(), Some(&self.app_theme), |theme| {
@@ -140,15 +900,68 @@ impl Application for ThemeColors {
             .spacing(5)
             .align_items(iced::Alignment::Center);
 
+        let pages = row!(
+            tab_button(
+                "Editor",
+                self.page == Page::Editor,
+                Message::SelectPage(Page::Editor)
+            ),
+            tab_button(
+                "Compare",
+                self.page == Page::Compare,
+                Message::SelectPage(Page::Compare)
+            ),
+            tab_button(
+                "Split View",
+                self.page == Page::Split,
+                Message::SelectPage(Page::Split)
+            ),
+            tab_button(
+                "Scales",
+                self.page == Page::Scales,
+                Message::SelectPage(Page::Scales)
+            ),
+            tab_button(
+                "Contrast",
+                self.page == Page::Contrast,
+                Message::SelectPage(Page::Contrast)
+            ),
+            tab_button(
+                "Import / Export",
+                self.page == Page::Exchange,
+                Message::SelectPage(Page::Exchange)
+            ),
+        )
+        .spacing(10);
+
         let top_container = container(
-            row!(label_and_app_theme_picker, label_and_working_theme_picker)
+            column!(
+                row!(
+                    pages,
+                    label_and_app_theme_picker,
+                    label_and_working_theme_picker
+                )
                 .spacing(20)
                 .align_items(iced::Alignment::Center),
+                text(self.status.as_deref().unwrap_or_default()).size(14),
+                self.restore_prompt()
+            )
+            .spacing(5)
+            .align_items(iced::Alignment::Center),
         )
         .center_x()
         .width(Length::Fill)
         .padding(10);
 
+        match self.page {
+            Page::Editor => {}
+            Page::Compare => return page_layout(column!(top_container, compare::view(self))),
+            Page::Split => return page_layout(column!(top_container, split::view(self))),
+            Page::Scales => return page_layout(column!(top_container, scale::view(self))),
+            Page::Contrast => return page_layout(column!(top_container, contrast::view(self))),
+            Page::Exchange => return page_layout(column!(top_container, exchange::view(self))),
+        }
+
         let palette = {
             let label = row!(
                 widget::Space::new(50, 1),
@@ -176,27 +989,37 @@ impl Application for ThemeColors {
                 .align_items(iced::Alignment::Center)
                 .spacing(10);
 
-            for (i, color) in self.palette.iter().enumerate() {
-                let color_view =
-                    container(column!())
-                        .width(150)
-                        .height(110)
-                        .style(container::Appearance {
-                            background: Some(Background::Color(*color)),
-                            ..container::Appearance::default()
-                        });
+            for (i, color) in palette_colors.iter().enumerate() {
+                let mut lock_marker = if self.palette_locked[i] {
+                    column!(text("Locked")
+                        .size(12)
+                        .style(theme::Text::Color(color.inverse())))
+                } else {
+                    column!()
+                };
+                if !self.gamut.contains(*color) {
+                    lock_marker = lock_marker.push(
+                        text(format!("Outside {}", self.gamut.name()))
+                            .size(12)
+                            .style(theme::Text::Color(color.inverse())),
+                    );
+                }
+                let color_view = container(lock_marker)
+                    .width(150)
+                    .height(110)
+                    .center_x()
+                    .center_y()
+                    .style(container::Appearance {
+                        background: Some(Background::Color(*color)),
+                        ..container::Appearance::default()
+                    });
 
-                let color_selector = button(color_view)
-                    .padding(2)
-                    .style(theme::Button::Text)
-                    .on_press(Message::SelectColor(Select::Palette(i)));
+                let color_selector = swatch(color_view, Select::Palette(i));
 
                 let mut container = container(color_selector);
 
-                if let Select::Palette(selected) = self.selected {
-                    if selected == i {
-                        container = container.style(selected_style)
-                    }
+                if self.selection.contains(&Select::Palette(i)) {
+                    container = container.style(selected_style)
                 }
 
                 match i {
@@ -238,42 +1061,74 @@ impl Application for ThemeColors {
             .width(Length::Fill);
             let color_strength: Column<'_, Message, Self::Theme, iced::Renderer> = column!(
                 widget::Space::new(1, 30),
-                container(text("Base")).height(110).width(50).center_y(),
-                container(text("Weak")).height(110).width(50).center_y(),
-                container(text("Strong")).height(110).width(50).center_y(),
+                container(header_button("Base", Message::SelectStrength(0)))
+                    .height(110)
+                    .width(50)
+                    .center_y(),
+                container(header_button("Weak", Message::SelectStrength(1)))
+                    .height(110)
+                    .width(50)
+                    .center_y(),
+                container(header_button("Strong", Message::SelectStrength(2)))
+                    .height(110)
+                    .width(50)
+                    .center_y(),
             )
             .spacing(10);
 
-            let mut background = column!(text("Background").height(30))
-                .spacing(10)
-                .align_items(iced::Alignment::Center);
-            let mut primary = column!(text("Primary").height(30))
+            let mut background =
+                column!(header_button("Background", Message::SelectRole(0)).height(30))
+                    .spacing(10)
+                    .align_items(iced::Alignment::Center);
+            let mut primary = column!(header_button("Primary", Message::SelectRole(1)).height(30))
                 .spacing(10)
                 .align_items(iced::Alignment::Center);
-            let mut secondary = column!(text("Secondary").height(30))
+            let mut secondary =
+                column!(header_button("Secondary", Message::SelectRole(2)).height(30))
+                    .spacing(10)
+                    .align_items(iced::Alignment::Center);
+            let mut success = column!(header_button("Success", Message::SelectRole(3)).height(30))
                 .spacing(10)
                 .align_items(iced::Alignment::Center);
-            let mut success = column!(text("Success").height(30))
-                .spacing(10)
-                .align_items(iced::Alignment::Center);
-            let mut danger = column!(text("Danger").height(30))
+            let mut danger = column!(header_button("Danger", Message::SelectRole(4)).height(30))
                 .spacing(10)
                 .align_items(iced::Alignment::Center);
 
-            for (i, color) in self.extended.iter().enumerate() {
-                let mut text = container(
-                    button(text("Text").style(theme::Text::Color(color[1])))
-                        .style(theme::Button::Text)
-                        .on_press(Message::SelectColor(Select::Extended((i, 1)))),
-                );
+            for (i, color) in extended_colors.iter().enumerate() {
+                let mut text_label = if self.extended_locked[i][1] {
+                    "Text (locked)".to_string()
+                } else {
+                    "Text".to_string()
+                };
+                if !self.gamut.contains(color[1]) {
+                    text_label.push_str(&format!(" (outside {})", self.gamut.name()));
+                }
+                let mut text = container(swatch(
+                    container(text(text_label).style(theme::Text::Color(color[1]))).padding([3, 8]),
+                    Select::Extended((i, 1)),
+                ));
 
-                if let Select::Extended((index, ii)) = self.selected {
-                    if index == i && ii == 1 {
-                        text = text.style(selected_style)
-                    }
+                if self.selection.contains(&Select::Extended((i, 1))) {
+                    text = text.style(selected_style)
+                }
+
+                let mut text_and_lock = column!(text).align_items(iced::Alignment::Center);
+                if self.extended_locked[i][0] {
+                    text_and_lock = text_and_lock.push(
+                        iced::widget::text("Locked")
+                            .size(12)
+                            .style(theme::Text::Color(color[0].inverse())),
+                    );
+                }
+                if !self.gamut.contains(color[0]) {
+                    text_and_lock = text_and_lock.push(
+                        iced::widget::text(format!("Outside {}", self.gamut.name()))
+                            .size(12)
+                            .style(theme::Text::Color(color[0].inverse())),
+                    );
                 }
 
-                let color_view = container(text)
+                let color_view = container(text_and_lock)
                     .center_x()
                     .center_y()
                     .width(150)
@@ -282,17 +1137,10 @@ impl Application for ThemeColors {
                         background: Some(iced::Background::Color(color[0])),
                         ..Default::default()
                     });
-                let mut select_color = container(
-                    button(color_view)
-                        .padding(2)
-                        .style(theme::Button::Text)
-                        .on_press(Message::SelectColor(Select::Extended((i, 0)))),
-                );
+                let mut select_color = container(swatch(color_view, Select::Extended((i, 0))));
 
-                if let Select::Extended((index, ii)) = self.selected {
-                    if index == i && ii == 0 {
-                        select_color = select_color.style(selected_style)
-                    }
+                if self.selection.contains(&Select::Extended((i, 0))) {
+                    select_color = select_color.style(selected_style)
                 }
 
                 match i {
@@ -480,9 +1328,95 @@ impl Application for ThemeColors {
 
         let red_green = row!(adjust_red, adjust_green).spacing(10);
         let blue_alpha = row!(adjust_blue, adjust_alpha).spacing(10);
-        let sliders = container(column!(red_green, blue_alpha).spacing(10))
-            .width(Length::Fill)
-            .center_x();
+
+        let tools = row!(
+            tab_button(
+                "Adjust Color",
+                self.tool == Tool::Adjust,
+                Message::SelectTool(Tool::Adjust)
+            ),
+            tab_button(
+                "Transform Theme",
+                self.tool == Tool::Transform,
+                Message::SelectTool(Tool::Transform)
+            ),
+            tab_button(
+                "Blend Themes",
+                self.tool == Tool::Blend,
+                Message::SelectTool(Tool::Blend)
+            ),
+        )
+        .spacing(10);
+
+        let adjust_mode = row!(
+            text(format!("{} selected", self.selection.len())).width(100),
+            text(format!("Keyboard: {}", self.channel)).width(150),
+            widget::radio(
+                "Absolute",
+                AdjustMode::Absolute,
+                Some(self.adjust_mode),
+                Message::SelectAdjustMode
+            ),
+            widget::radio(
+                "Relative",
+                AdjustMode::Relative,
+                Some(self.adjust_mode),
+                Message::SelectAdjustMode
+            ),
+        )
+        .spacing(20)
+        .align_items(iced::Alignment::Center);
+
+        let tool_content = match self.tool {
+            Tool::Adjust => column!(adjust_mode, red_green, blue_alpha, self.oklch_view())
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            Tool::Transform => self.transform_view(),
+            Tool::Blend => blend::view(self),
+        };
+
+        let sliders = container(
+            column!(tools, tool_content)
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+        )
+        .width(Length::Fill)
+        .center_x();
+
+        let has_extended_selected = self
+            .selection
+            .iter()
+            .any(|select| matches!(select, Select::Extended(_)));
+
+        let edit = container(
+            row!(
+                action_button("Undo")
+                    .on_press_maybe(self.history.can_undo().then_some(Message::Undo))
+                    .width(100),
+                action_button("Redo")
+                    .on_press_maybe(self.history.can_redo().then_some(Message::Redo))
+                    .width(100),
+                action_button("Copy")
+                    .on_press(Message::CopySelected)
+                    .width(100),
+                action_button("Paste")
+                    .on_press(Message::PasteSelected)
+                    .width(100),
+                action_button("Swap Color/Text")
+                    .on_press_maybe(has_extended_selected.then_some(Message::SwapColorAndText))
+                    .width(150),
+                action_button(if self.is_selected_locked() {
+                    "Unlock"
+                } else {
+                    "Lock"
+                })
+                .on_press(Message::ToggleLockSelected)
+                .width(100),
+            )
+            .spacing(20),
+        )
+        .width(Length::Fill)
+        .center_x();
 
         let reset = container(
             row!(
@@ -535,16 +1469,66 @@ impl Application for ThemeColors {
         .width(Length::Fill)
         .center_x();
 
-        let content = column!(top_container, palette, extended, sliders, reset)
-            .align_items(iced::Alignment::Center)
-            .spacing(15);
+        let drag_hint = text(match self.dragging {
+            Some(_) => "Release on another color to copy it, hold Alt to swap",
+            None => "",
+        })
+        .size(14);
 
-        widget::container(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .align_x(iced::alignment::Horizontal::Left)
-            .align_y(iced::alignment::Vertical::Top)
-            .into()
+        let file = row!(
+            text_input("Theme file", &self.file_path)
+                .on_input(Message::FilePathChanged)
+                .width(300),
+            button(text("Open")).on_press(Message::OpenFile),
+            button(text("Save")).on_press(Message::SaveFile),
+            widget::pick_list(
+                self.preferences.recent_files.as_slice(),
+                None::<String>,
+                Message::OpenRecent
+            )
+            .placeholder("Recent files"),
+        )
+        .spacing(10)
+        .align_items(iced::Alignment::Center);
+
+        let content = column!(
+            top_container,
+            file,
+            palette,
+            extended,
+            drag_hint,
+            sliders,
+            edit,
+            reset
+        );
+
+        page_layout(content)
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let events = iced::event::listen_with(|event, _status| match event {
+            Event::Keyboard(iced::keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            Event::Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left)) => {
+                Some(Message::DragEnded)
+            }
+            Event::Window(_, iced::window::Event::CloseRequested) => Some(Message::CloseRequested),
+            Event::Window(_, iced::window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            Event::Window(_, iced::window::Event::Moved { x, y }) => {
+                Some(Message::WindowMoved(x, y))
+            }
+            _ => None,
+        });
+
+        Subscription::batch([
+            events,
+            iced::keyboard::on_key_press(shortcuts::handle_key_press),
+            iced::time::every(AUTOSAVE_INTERVAL).map(|_| Message::Autosave),
+            watch::subscription(self.watched_file.as_ref()),
+        ])
     }
 
     fn title(&self) -> String {
@@ -557,186 +1541,490 @@ impl Application for ThemeColors {
 }
 
 impl ThemeColors {
-    fn adjust_selected_red_color(&mut self, mut new_value: f32) {
-        if new_value > 1.0 {
-            new_value = 1.0;
-        } else if new_value < 0.0 {
-            new_value = 0.0;
+    fn transform_view(&self) -> Column<'_, Message> {
+        let transform_slider = |label: &'static str,
+                                range: std::ops::RangeInclusive<f32>,
+                                value: f32,
+                                step: f32,
+                                on_change: fn(f32) -> Message| {
+            row!(
+                text(label).width(100),
+                widget::Slider::new(range, value, on_change)
+                    .step(step)
+                    .width(300),
+                text(format!("{:.2}", value)).width(50)
+            )
+            .spacing(5)
+        };
+
+        let hue = transform_slider(
+            "Hue",
+            -180.0..=180.0,
+            self.transform.hue,
+            1.0,
+            Message::TransformHue,
+        );
+        let chroma = transform_slider(
+            "Saturation",
+            0.0..=2.0,
+            self.transform.chroma,
+            0.01,
+            Message::TransformChroma,
+        );
+        let lightness = transform_slider(
+            "Lightness",
+            -0.5..=0.5,
+            self.transform.lightness,
+            0.005,
+            Message::TransformLightness,
+        );
+        let temperature = transform_slider(
+            "Temperature",
+            -1.0..=1.0,
+            self.transform.temperature,
+            0.01,
+            Message::TransformTemperature,
+        );
+
+        let has_changes = !self.transform.is_identity();
+        let buttons = row!(
+            button(
+                text("Apply")
+                    .width(Length::Fill)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center)
+            )
+            .on_press_maybe(has_changes.then_some(Message::ApplyTransform))
+            .width(150),
+            button(
+                text("Cancel")
+                    .width(Length::Fill)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center)
+            )
+            .on_press_maybe(has_changes.then_some(Message::CancelTransform))
+            .width(150),
+        )
+        .spacing(30);
+
+        column!(hue, chroma, lightness, temperature, buttons)
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+    }
+
+    /// OKLCH sliders for the selected color, edits are not clamped so the gamut is checked here
+    fn oklch_view(&self) -> Column<'_, Message> {
+        let color = self.color(self.selected);
+        let lch = Oklch::from_color(color);
+
+        let oklch_slider =
+            |label: &'static str, value: f32, display: String, on_change: fn(f32) -> Message| {
+                row!(
+                    text(label).width(80),
+                    widget::Slider::new(0.0..=1.0, value, on_change)
+                        .step(0.002)
+                        .width(220),
+                    text(display).width(60),
+                )
+                .spacing(5)
+                .align_items(iced::Alignment::Center)
+            };
+
+        let lightness = oklch_slider(
+            "Lightness",
+            lch.l,
+            format!("{:.1}%", lch.l * 100.0),
+            Message::AdjustOkLightness,
+        );
+        let chroma = oklch_slider(
+            "Chroma",
+            lch.c / MAX_OK_CHROMA,
+            format!("{:.3}", lch.c),
+            Message::AdjustOkChroma,
+        );
+        let hue = oklch_slider(
+            "Hue",
+            lch.h / 360.0,
+            format!("{:.1}", lch.h),
+            Message::AdjustOkHue,
+        );
+
+        let out_of_gamut = self
+            .selection
+            .iter()
+            .filter(|select| !self.gamut.contains(self.color(**select)))
+            .count();
+        let warning = if self.gamut.contains(color) {
+            text(format!("Inside {}", self.gamut.name()))
+        } else {
+            text(format!("Outside {}", self.gamut.name()))
+                .style(theme::Text::Color(self.app_theme.palette().danger))
+        };
+
+        let gamut = Gamut::ALL
+            .iter()
+            .fold(row!(text("Gamut")).spacing(20), |row, gamut| {
+                row.push(widget::radio(
+                    gamut.name(),
+                    *gamut,
+                    Some(self.gamut),
+                    Message::SelectGamut,
+                ))
+            });
+
+        column!(
+            text("OKLCH").size(18),
+            row!(column!(lightness, chroma, hue).spacing(5), warning)
+                .spacing(20)
+                .align_items(iced::Alignment::Center),
+            row!(
+                gamut.align_items(iced::Alignment::Center),
+                action_button("Fit to Gamut")
+                    .on_press_maybe((out_of_gamut > 0).then_some(Message::FitSelectionToGamut))
+                    .width(120),
+            )
+            .spacing(20)
+            .align_items(iced::Alignment::Center),
+        )
+        .spacing(10)
+        .align_items(iced::Alignment::Center)
+    }
+
+    fn transformed_colors(&self) -> ([Color; 5], [[Color; 2]; 15]) {
+        let mut palette = self.palette;
+        let mut extended = self.extended;
+
+        if self.transform.is_identity() {
+            return (palette, extended);
+        }
+
+        for (color, locked) in palette.iter_mut().zip(self.palette_locked) {
+            if !locked {
+                *color = self.transform.apply(*color);
+            }
+        }
+        for (pair, locked) in extended.iter_mut().zip(self.extended_locked) {
+            for (color, locked) in pair.iter_mut().zip(locked) {
+                if !locked {
+                    *color = self.transform.apply(*color);
+                }
+            }
         }
 
+        (palette, extended)
+    }
+
+    fn is_selected_locked(&self) -> bool {
         match self.selected {
-            Select::Palette(index) => self.palette[index].r = new_value,
-            Select::Extended((index1, index2)) => self.extended[index1][index2].r = new_value,
+            Select::Palette(index) => self.palette_locked[index],
+            Select::Extended((index1, index2)) => self.extended_locked[index1][index2],
         }
     }
 
-    fn adjust_selected_green_color(&mut self, mut new_value: f32) {
-        if new_value > 1.0 {
-            new_value = 1.0;
-        } else if new_value < 0.0 {
-            new_value = 0.0;
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            palette: self.palette,
+            extended: self.extended,
         }
+    }
 
-        match self.selected {
-            Select::Palette(index) => self.palette[index].g = new_value,
-            Select::Extended((index1, index2)) => self.extended[index1][index2].g = new_value,
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.palette = snapshot.palette;
+        self.extended = snapshot.extended;
+        self.editing_selected = false;
+    }
+
+    fn record_history(&mut self) {
+        self.history.record(self.snapshot());
+        self.editing_selected = false;
+    }
+
+    /// Consecutive slider edits of the same color are recorded as a single undo step
+    fn record_selected_edit(&mut self) {
+        if !self.editing_selected {
+            self.history.record(self.snapshot());
+            self.editing_selected = true;
         }
     }
 
-    fn adjust_selected_blue_color(&mut self, mut new_value: f32) {
-        if new_value > 1.0 {
-            new_value = 1.0;
-        } else if new_value < 0.0 {
-            new_value = 0.0;
+    /// Ctrl-click toggles a color in the selection, Shift-click selects the range from the
+    /// primary selection to the clicked color
+    fn select_color(&mut self, selected: Select) {
+        self.editing_selected = false;
+
+        if self.modifiers.command() {
+            if let Some(position) = self.selection.iter().position(|s| *s == selected) {
+                if self.selection.len() > 1 {
+                    self.selection.remove(position);
+                    if self.selected == selected {
+                        self.selected = self.selection[0];
+                    }
+                }
+            } else {
+                self.selection.push(selected);
+                self.selected = selected;
+            }
+        } else if self.modifiers.shift() {
+            self.selection = selection_range(self.selected, selected);
+            self.selection.retain(|s| *s != self.selected);
+            self.selection.insert(0, self.selected);
+        } else {
+            self.selected = selected;
+            self.selection = vec![selected];
         }
+    }
 
-        match self.selected {
-            Select::Palette(index) => self.palette[index].b = new_value,
-            Select::Extended((index1, index2)) => self.extended[index1][index2].b = new_value,
+    fn select_single(&mut self, selected: Select) {
+        self.editing_selected = false;
+        self.selected = selected;
+        self.selection = vec![selected];
+    }
+
+    /// Palette columns line up with the Extended role columns, moving up from the first
+    /// Extended row goes back to the palette
+    fn navigate(&mut self, direction: Direction) {
+        let next = match (self.selected, direction) {
+            (Select::Palette(index), Direction::Left) => Select::Palette(index.saturating_sub(1)),
+            (Select::Palette(index), Direction::Right) => Select::Palette((index + 1).min(4)),
+            (Select::Palette(_), Direction::Up) => self.selected,
+            (Select::Palette(index), Direction::Down) => Select::Extended((index * 3, 0)),
+            (Select::Extended((index, pair)), direction) => {
+                let (role, strength) = (index / 3, index % 3);
+                match direction {
+                    Direction::Left => {
+                        Select::Extended((role.saturating_sub(1) * 3 + strength, pair))
+                    }
+                    Direction::Right => Select::Extended(((role + 1).min(4) * 3 + strength, pair)),
+                    Direction::Up if strength == 0 => Select::Palette(role),
+                    Direction::Up => Select::Extended((index - 1, pair)),
+                    Direction::Down => Select::Extended((role * 3 + (strength + 1).min(2), pair)),
+                }
+            }
+        };
+
+        self.select_single(next);
+    }
+
+    fn select_group(&mut self, group: Vec<Select>) {
+        self.editing_selected = false;
+
+        if self.modifiers.command() || self.modifiers.shift() {
+            for select in group {
+                if !self.selection.contains(&select) {
+                    self.selection.push(select);
+                }
+            }
+        } else {
+            self.selected = group[0];
+            self.selection = group;
         }
     }
 
-    fn adjust_selected_alpha_color(&mut self, mut new_value: f32) {
-        if new_value > 1.0 {
-            new_value = 1.0;
-        } else if new_value < 0.0 {
-            new_value = 0.0;
+    /// Dropping a color on another slot copies it there, with Alt held the two colors are swapped
+    fn drop_color(&mut self, source: Select, target: Select) {
+        self.record_history();
+
+        let source_color = self.color(source);
+        if self.modifiers.alt() {
+            *self.color_mut(source) = self.color(target);
         }
+        *self.color_mut(target) = source_color;
+    }
 
-        match self.selected {
-            Select::Palette(index) => self.palette[index].a = new_value,
-            Select::Extended((index1, index2)) => self.extended[index1][index2].a = new_value,
+    /// Writes an exported file into the export directory and reports the outcome in the status
+    fn export(&mut self, file_name: &str, contents: impl AsRef<[u8]>) {
+        let path = std::path::Path::new(&self.export_directory).join(file_name);
+        self.status = match std::fs::write(&path, contents) {
+            Ok(()) => Some(format!("Exported {}", path.display())),
+            Err(error) => Some(format!("Could not export {}: {}", path.display(), error)),
+        };
+    }
+
+    fn restore_prompt(&self) -> iced::Element<'_, Message> {
+        if self.reload_conflict.is_some() {
+            return row!(
+                text("The theme file changed on disk and you have unsaved edits"),
+                button(text("Reload From Disk")).on_press(Message::ReloadFromDisk),
+                button(text("Keep My Edits"))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::KeepEdits)
+            )
+            .spacing(10)
+            .align_items(iced::Alignment::Center)
+            .into();
+        }
+        if self.pending_session.is_none() {
+            return column!().into();
         }
+
+        row!(
+            text("Restore previous session?"),
+            button(text("Restore")).on_press(Message::RestoreSession),
+            button(text("Discard"))
+                .style(theme::Button::Secondary)
+                .on_press(Message::DiscardSession)
+        )
+        .spacing(10)
+        .align_items(iced::Alignment::Center)
+        .into()
     }
 
-    fn generate_extended_from_palette(&mut self) {
-        let palette = self.palette_from_palette_array();
+    fn session(&self) -> Session {
+        Session {
+            palette: self.palette,
+            extended: self.extended,
+            palette_locked: self.palette_locked,
+            extended_locked: self.extended_locked,
+            working_theme: self.working_theme.clone(),
+            app_theme: self.app_theme.clone(),
+            selected: self.selected,
+            selection: self.selection.clone(),
+            undo: self.history.undo_stack().to_vec(),
+            redo: self.history.redo_stack().to_vec(),
+            file_path: self.file_path.clone(),
+        }
+    }
 
-        let extended = Extended::generate(palette);
-        self.extended = Self::populate_extended_array(&extended);
+    fn restore_session(&mut self, session: Session) {
+        self.palette = session.palette;
+        self.extended = session.extended;
+        self.palette_locked = session.palette_locked;
+        self.extended_locked = session.extended_locked;
+        if !Theme::ALL.contains(&session.working_theme) {
+            self.themes[0] = session.working_theme.clone();
+        }
+        self.working_theme = session.working_theme;
+        self.app_theme = session.app_theme;
+        self.selected = session.selected;
+        self.selection = session.selection;
+        self.history = History::from_stacks(session.undo, session.redo);
+        self.file_path = session.file_path;
     }
 
-    fn palette_from_palette_array(&self) -> Palette {
-        Palette {
-            background: self.palette[0],
-            primary: self.palette[1],
-            text: self.palette[2],
-            success: self.palette[3],
-            danger: self.palette[4],
+    /// Writes the session when it changed since the last autosave. A session waiting to be restored
+    /// is kept in memory, so edits made before answering the prompt are saved too
+    fn autosave(&mut self) {
+        let json = self.session().to_json();
+        if json == self.last_autosave {
+            return;
+        }
+        match Session::save(&json) {
+            Ok(()) => self.last_autosave = json,
+            Err(error) => self.status = Some(format!("Could not autosave: {}", error)),
         }
     }
 
-    fn extendedpalette_from_colors_array(&self, palette: Palette) -> Extended {
-        let generated_extended = Extended::generate(palette);
-        Extended {
-            background: theme::palette::Background {
-                base: Pair {
-                    color: self.extended[0][0],
-                    text: self.extended[0][1],
-                },
-                weak: Pair {
-                    color: self.extended[1][0],
-                    text: self.extended[1][1],
-                },
-                strong: Pair {
-                    color: self.extended[2][0],
-                    text: self.extended[2][1],
-                },
-            },
-            primary: Primary {
-                base: Pair {
-                    color: self.extended[3][0],
-                    text: self.extended[3][1],
-                },
-                weak: Pair {
-                    color: self.extended[4][0],
-                    text: self.extended[4][1],
-                },
-                strong: Pair {
-                    color: self.extended[5][0],
-                    text: self.extended[5][1],
-                },
-            },
-            secondary: Secondary {
-                base: Pair {
-                    color: self.extended[6][0],
-                    text: self.extended[6][1],
-                },
-                weak: Pair {
-                    color: self.extended[7][0],
-                    text: self.extended[7][1],
-                },
-                strong: Pair {
-                    color: self.extended[8][0],
-                    text: self.extended[8][1],
-                },
-            },
-            success: Success {
-                base: Pair {
-                    color: self.extended[9][0],
-                    text: self.extended[9][1],
-                },
-                weak: Pair {
-                    color: self.extended[10][0],
-                    text: self.extended[10][1],
-                },
-                strong: Pair {
-                    color: self.extended[11][0],
-                    text: self.extended[11][1],
-                },
-            },
-            danger: Danger {
-                base: Pair {
-                    color: self.extended[12][0],
-                    text: self.extended[12][1],
-                },
-                weak: Pair {
-                    color: self.extended[13][0],
-                    text: self.extended[13][1],
-                },
-                strong: Pair {
-                    color: self.extended[14][0],
-                    text: self.extended[14][1],
-                },
-            },
-            is_dark: generated_extended.is_dark,
+    /// Loads a changed file as an undoable step, the reloaded colors become the saved state
+    fn reload_document(&mut self, document: ThemeDocument) {
+        self.record_history();
+        self.load_document(document);
+        let saved = self.snapshot();
+        if let Some(watched) = &mut self.watched_file {
+            watched.saved = saved;
         }
     }
 
-    fn populate_palette_array(palette: Palette) -> [Color; 5] {
-        [
-            palette.background,
-            palette.primary,
-            palette.text,
-            palette.success,
-            palette.danger,
-        ]
+    /// Writes the preferences when they changed since they were last saved
+    fn save_preferences(&mut self) {
+        let preferences = Preferences {
+            app_theme: Some(session::ThemeEntry::from_theme(&self.app_theme)),
+            working_theme: Some(session::ThemeEntry::from_theme(&self.working_theme)),
+            color_space: self.blend_space,
+            adjust_mode: self.adjust_mode,
+            gamut: self.gamut,
+            contrast_method: self.contrast_method,
+            export_directory: self.export_directory.clone(),
+            blend_directory: self.blend_directory.clone(),
+            blend_steps: self.blend_steps.clone(),
+            ..self.preferences.clone()
+        };
+
+        if preferences == self.last_saved_preferences {
+            return;
+        }
+        match preferences.save() {
+            Ok(()) => self.last_saved_preferences = preferences,
+            Err(error) => self.status = Some(format!("Could not save preferences: {}", error)),
+        }
     }
 
-    fn populate_extended_array(palette: &Extended) -> [[Color; 2]; 15] {
-        [
-            [palette.background.base.color, palette.background.base.text],
-            [palette.background.weak.color, palette.background.weak.text],
-            [
-                palette.background.strong.color,
-                palette.background.strong.text,
-            ],
-            [palette.primary.base.color, palette.primary.base.text],
-            [palette.primary.weak.color, palette.primary.weak.text],
-            [palette.primary.strong.color, palette.primary.strong.text],
-            [palette.secondary.base.color, palette.secondary.base.text],
-            [palette.secondary.weak.color, palette.secondary.weak.text],
-            [
-                palette.secondary.strong.color,
-                palette.secondary.strong.text,
-            ],
-            [palette.success.base.color, palette.success.base.text],
-            [palette.success.weak.color, palette.success.weak.text],
-            [palette.success.strong.color, palette.success.strong.text],
-            [palette.danger.base.color, palette.danger.base.text],
-            [palette.danger.weak.color, palette.danger.weak.text],
-            [palette.danger.strong.color, palette.danger.strong.text],
-        ]
+    fn document(&self) -> ThemeDocument {
+        let name = std::path::Path::new(&self.file_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Custom".to_string());
+
+        ThemeDocument {
+            name,
+            is_dark: Extended::generate(self.palette_from_palette_array()).is_dark,
+            palette: self.palette,
+            extended: self.extended,
+        }
+    }
+
+    /// A loaded document becomes the working theme, so resetting goes back to the file contents
+    fn load_document(&mut self, document: ThemeDocument) {
+        self.import_report.clear();
+        let theme = document.to_theme();
+        self.palette = document.palette;
+        self.extended = document.extended;
+        self.themes[0] = theme.clone();
+        self.working_theme = theme;
+    }
+
+    /// The color of the slot in the working theme, before any edits
+    fn working_color(&self, select: Select) -> Color {
+        match select {
+            Select::Palette(index) => {
+                arrays::populate_palette_array(self.working_theme.palette())[index]
+            }
+            Select::Extended((index1, index2)) => {
+                arrays::populate_extended_array(self.working_theme.extended_palette())[index1]
+                    [index2]
+            }
+        }
+    }
+
+    fn color(&self, select: Select) -> Color {
+        match select {
+            Select::Palette(index) => self.palette[index],
+            Select::Extended((index1, index2)) => self.extended[index1][index2],
+        }
+    }
+
+    fn color_mut(&mut self, select: Select) -> &mut Color {
+        match select {
+            Select::Palette(index) => &mut self.palette[index],
+            Select::Extended((index1, index2)) => &mut self.extended[index1][index2],
+        }
+    }
+
+    fn adjust_selected_color(&mut self, channel: Channel, new_value: f32) {
+        let new_value = new_value.clamp(0.0, 1.0);
+        let delta = new_value - channel.get(self.color(self.selected));
+
+        for select in self.selection.clone() {
+            let adjust_mode = self.adjust_mode;
+            let color = self.color_mut(select);
+            let value = match adjust_mode {
+                AdjustMode::Absolute => new_value,
+                AdjustMode::Relative => (channel.get(*color) + delta).clamp(0.0, 1.0),
+            };
+            channel.set(color, value);
+        }
+    }
+
+    fn generate_extended_from_palette(&mut self) {
+        let palette = self.palette_from_palette_array();
+
+        let extended = Extended::generate(palette);
+        self.extended = arrays::populate_extended_array(&extended);
+    }
+
+    fn palette_from_palette_array(&self) -> Palette {
+        arrays::palette_from_array(&self.palette)
+    }
+
+    fn extendedpalette_from_colors_array(&self, palette: Palette) -> Extended {
+        arrays::extended_from_colors(palette, &self.extended)
     }
 
     fn themes_array() -> [Theme; 22] {
@@ -767,6 +2055,86 @@ impl ThemeColors {
     }
 }
 
+/// All colors in the rectangle spanned by `from` and `to`, only the clicked color if they are in
+/// different grids
+fn selection_range(from: Select, to: Select) -> Vec<Select> {
+    match (from, to) {
+        (Select::Palette(from), Select::Palette(to)) => {
+            (from.min(to)..=from.max(to)).map(Select::Palette).collect()
+        }
+        (Select::Extended((from_index, from_pair)), Select::Extended((to_index, to_pair))) => {
+            let (from_role, from_strength) = (from_index / 3, from_index % 3);
+            let (to_role, to_strength) = (to_index / 3, to_index % 3);
+            let mut range = Vec::new();
+
+            for role in from_role.min(to_role)..=from_role.max(to_role) {
+                for strength in from_strength.min(to_strength)..=from_strength.max(to_strength) {
+                    for pair in from_pair.min(to_pair)..=from_pair.max(to_pair) {
+                        range.push(Select::Extended((role * 3 + strength, pair)));
+                    }
+                }
+            }
+            range
+        }
+        _ => vec![to],
+    }
+}
+
+fn swatch<'a>(
+    content: impl Into<iced::Element<'a, Message>>,
+    select: Select,
+) -> widget::MouseArea<'a, Message> {
+    widget::mouse_area(container(content).padding(2))
+        .on_press(Message::SwatchPressed(select))
+        .on_release(Message::SwatchReleased(select))
+}
+
+fn action_button(label: &str) -> widget::Button<'_, Message> {
+    button(
+        text(label)
+            .width(Length::Fill)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+}
+
+fn header_button(label: &str, on_press: Message) -> widget::Button<'_, Message> {
+    button(text(label))
+        .padding(0)
+        .style(theme::Button::Text)
+        .on_press(on_press)
+}
+
+fn page_layout<'a>(content: Column<'a, Message>) -> iced::Element<'a, Message> {
+    let content = content
+        .align_items(iced::Alignment::Center)
+        .spacing(15)
+        .padding([0, 0, 15, 0]);
+
+    widget::container(widget::scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(iced::alignment::Horizontal::Left)
+        .align_y(iced::alignment::Vertical::Top)
+        .into()
+}
+
+fn tab_button(label: &str, active: bool, on_press: Message) -> widget::Button<'_, Message> {
+    let style = if active {
+        theme::Button::Primary
+    } else {
+        theme::Button::Secondary
+    };
+
+    button(
+        text(label)
+            .width(Length::Fill)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+    )
+    .style(style)
+    .on_press(on_press)
+    .width(150)
+}
+
 fn selected_style(theme: &Theme) -> container::Appearance {
     let palette = theme.extended_palette();
 
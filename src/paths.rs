@@ -0,0 +1,46 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const APP_DIRECTORY: &str = "theme_colors";
+
+/// `$XDG_CONFIG_HOME/theme_colors`, or `~/.config/theme_colors` when it is not set
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_STATE_HOME/theme_colors`, or `~/.local/state/theme_colors` when it is not set
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+fn xdg_dir(variable: &str, fallback: &str) -> Option<PathBuf> {
+    let base = env::var_os(variable)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(fallback))
+        })?;
+
+    Some(base.join(APP_DIRECTORY))
+}
+
+/// Writes a temporary file next to `path` and renames it into place, so a crash midway never
+/// leaves a truncated file behind
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temporary = path.with_extension("tmp");
+    let mut file = File::create(&temporary)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    fs::rename(&temporary, path)
+}
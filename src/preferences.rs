@@ -0,0 +1,100 @@
+use std::{fs, io, path::PathBuf};
+
+use iced::Theme;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    blend::BlendSpace, color::Gamut, contrast::ContrastMethod, paths, session::ThemeEntry,
+    AdjustMode,
+};
+
+const FILE_NAME: &str = "preferences.toml";
+const MAX_RECENT_FILES: usize = 10;
+
+/// User preferences that outlive a session, loaded before the window is created
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub app_theme: Option<ThemeEntry>,
+    pub working_theme: Option<ThemeEntry>,
+    pub window: Window,
+    pub color_space: BlendSpace,
+    pub adjust_mode: AdjustMode,
+    pub gamut: Gamut,
+    pub contrast_method: ContrastMethod,
+    pub export_directory: String,
+    pub blend_directory: String,
+    pub blend_steps: String,
+    pub recent_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Window {
+    pub width: f32,
+    pub height: f32,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            app_theme: None,
+            working_theme: None,
+            window: Window {
+                width: 1024.0,
+                height: 900.0,
+                x: None,
+                y: None,
+            },
+            color_space: BlendSpace::Oklab,
+            adjust_mode: AdjustMode::Absolute,
+            gamut: Gamut::Srgb,
+            contrast_method: ContrastMethod::Wcag2,
+            export_directory: String::new(),
+            blend_directory: String::new(),
+            blend_steps: "5".to_string(),
+            recent_files: Vec::new(),
+        }
+    }
+}
+
+impl Preferences {
+    pub fn path() -> Option<PathBuf> {
+        paths::config_dir().map(|directory| directory.join(FILE_NAME))
+    }
+
+    /// Missing or unreadable preferences fall back to the defaults
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|input| toml::from_str(&input).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no config directory available")
+        })?;
+        let contents = toml::to_string(self).expect("preferences serialize to toml");
+        paths::write_atomic(&path, contents.as_bytes())
+    }
+
+    pub fn app_theme(&self) -> Option<Theme> {
+        self.app_theme.as_ref()?.to_theme().ok()
+    }
+
+    pub fn working_theme(&self) -> Option<Theme> {
+        self.working_theme.as_ref()?.to_theme().ok()
+    }
+
+    /// Moves `path` to the front of the recent files
+    pub fn add_recent_file(&mut self, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+        self.recent_files.retain(|recent| recent != path);
+        self.recent_files.insert(0, path.to_string());
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+}
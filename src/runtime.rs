@@ -0,0 +1,36 @@
+//! Loads theme files at runtime instead of compiling the colors into the app.
+//!
+//! ```no_run
+//! let theme = theme_colors::runtime::load_or("theme.toml", "Dark");
+//! ```
+
+use std::path::Path;
+
+use iced::Theme;
+
+use crate::document::{DocumentError, ThemeDocument};
+
+/// Reads a theme file, every palette and Extended color must be present and a valid hex color.
+/// The result is [`ThemeDocument::to_theme`], the theme the editor shows for the file.
+pub fn load(path: impl AsRef<Path>) -> Result<Theme, DocumentError> {
+    Ok(ThemeDocument::load(path)?.to_theme())
+}
+
+/// Same as [`load`] for a theme file that is already in memory
+pub fn from_toml(input: &str) -> Result<Theme, DocumentError> {
+    Ok(ThemeDocument::from_toml(input)?.to_theme())
+}
+
+/// [`load`], or the built-in theme called `fallback` when the file is missing or invalid.
+/// `Theme::Dark` is used when `fallback` is not the name of a built-in theme either.
+pub fn load_or(path: impl AsRef<Path>, fallback: &str) -> Theme {
+    load(path).unwrap_or_else(|_| builtin(fallback).unwrap_or(Theme::Dark))
+}
+
+/// The built-in theme with this display name, like "Dark" or "Tokyo Night"
+pub fn builtin(name: &str) -> Option<Theme> {
+    Theme::ALL
+        .iter()
+        .find(|theme| theme.to_string() == name)
+        .cloned()
+}
@@ -0,0 +1,417 @@
+use iced::{theme::palette::Extended, Color};
+use syn::{visit::Visit, Expr, ExprStruct, Lit, Member};
+
+use crate::{
+    arrays, document::ThemeDocument, Select, PAIR_NAMES, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES,
+};
+
+/// The colors found in `Palette` and `Extended` literals, with a line for every slot that could not be read
+pub struct SourceImport {
+    pub document: ThemeDocument,
+    pub unresolved: Vec<String>,
+}
+
+#[derive(Default)]
+struct Literals {
+    palettes: Vec<ExprStruct>,
+    extended: Vec<ExprStruct>,
+}
+
+impl<'ast> Visit<'ast> for Literals {
+    fn visit_expr_struct(&mut self, node: &'ast ExprStruct) {
+        match node
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+        {
+            Some(name) if name == "Palette" => self.palettes.push(node.clone()),
+            Some(name) if name == "Extended" => self.extended.push(node.clone()),
+            _ => {}
+        }
+        syn::visit::visit_expr_struct(self, node);
+    }
+}
+
+/// Slots that are not found keep the colors in `current`, they are listed in `unresolved`
+pub fn import(
+    input: &str,
+    name: &str,
+    current: (&[Color; 5], &[[Color; 2]; 15]),
+) -> Result<SourceImport, String> {
+    let file = syn::parse_file(input).map_err(|error| error.to_string())?;
+    let mut literals = Literals::default();
+    literals.visit_file(&file);
+
+    if literals.palettes.is_empty() && literals.extended.is_empty() {
+        return Err("no Palette or Extended struct literal found".to_string());
+    }
+
+    let mut palette = *current.0;
+    let mut extended = *current.1;
+    let mut unresolved = Vec::new();
+
+    for (kind, count) in [
+        ("Palette", literals.palettes.len()),
+        ("Extended", literals.extended.len()),
+    ] {
+        if count > 1 {
+            unresolved.push(format!(
+                "{count} {kind} literals found, only the first was read"
+            ));
+        }
+    }
+
+    match literals.palettes.first() {
+        Some(literal) => {
+            for (index, name) in PALETTE_NAMES.iter().enumerate() {
+                let select = Select::Palette(index);
+                match field(literal, name)
+                    .ok_or("missing")
+                    .and_then(resolve_color)
+                {
+                    Ok(color) => palette[index] = color,
+                    Err(reason) => unresolved.push(format!("{}: {}", select.name(), reason)),
+                }
+            }
+        }
+        None => unresolved.push("no Palette literal, the current palette was kept".to_string()),
+    }
+
+    match literals.extended.first() {
+        Some(literal) => {
+            for (role, role_name) in ROLE_NAMES.iter().enumerate() {
+                for (strength, strength_name) in STRENGTH_NAMES.iter().enumerate() {
+                    let index = role * 3 + strength;
+                    let pair = field(literal, role_name)
+                        .and_then(as_struct)
+                        .and_then(|role| field(role, strength_name));
+
+                    for (pair_index, pair_name) in PAIR_NAMES.iter().enumerate() {
+                        let select = Select::Extended((index, pair_index));
+                        match pair
+                            .ok_or("missing")
+                            .and_then(|pair| pair_color(pair, pair_index, pair_name))
+                        {
+                            Ok(color) => extended[index][pair_index] = color,
+                            Err(reason) => {
+                                unresolved.push(format!("{}: {}", select.name(), reason))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None => {
+            extended = arrays::populate_extended_array(&Extended::generate(
+                arrays::palette_from_array(&palette),
+            ));
+            unresolved.push("no Extended literal, it was generated from the palette".to_string());
+        }
+    }
+
+    Ok(SourceImport {
+        document: ThemeDocument {
+            name: name.to_string(),
+            is_dark: Extended::generate(arrays::palette_from_array(&palette)).is_dark,
+            palette,
+            extended,
+        },
+        unresolved,
+    })
+}
+
+fn field<'a>(literal: &'a ExprStruct, name: &str) -> Option<&'a Expr> {
+    literal.fields.iter().find_map(|field| match &field.member {
+        Member::Named(ident) if ident == name => Some(&field.expr),
+        _ => None,
+    })
+}
+
+fn as_struct(expr: &Expr) -> Option<&ExprStruct> {
+    match expr {
+        Expr::Struct(literal) => Some(literal),
+        _ => None,
+    }
+}
+
+/// A `Pair { color, text }` literal, `Pair::new` adjusts the text color at runtime so only its color is read
+fn pair_color(expr: &Expr, index: usize, name: &str) -> Result<Color, &'static str> {
+    match expr {
+        Expr::Struct(literal) => field(literal, name)
+            .ok_or("missing")
+            .and_then(resolve_color),
+        Expr::Call(call) if index == 0 && path_ends_with(&call.func, &["Pair", "new"]) => {
+            call.args.first().ok_or("missing").and_then(resolve_color)
+        }
+        Expr::Call(call) if path_ends_with(&call.func, &["Pair", "new"]) => {
+            Err("computed by Pair::new at runtime")
+        }
+        _ => Err("not a Pair literal"),
+    }
+}
+
+fn path_ends_with(expr: &Expr, names: &[&str]) -> bool {
+    match expr {
+        Expr::Path(path) => ends_with(&path.path, names),
+        _ => false,
+    }
+}
+
+fn ends_with(path: &syn::Path, names: &[&str]) -> bool {
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    segments.len() >= names.len()
+        && segments[segments.len() - names.len()..]
+            .iter()
+            .zip(names)
+            .all(|(segment, name)| segment == name)
+}
+
+fn resolve_color(expr: &Expr) -> Result<Color, &'static str> {
+    match expr {
+        Expr::Call(call) => {
+            let args: Vec<f32> = call
+                .args
+                .iter()
+                .map(number)
+                .collect::<Option<_>>()
+                .ok_or("arguments are not numeric literals")?;
+
+            let constructor = ["from_rgb", "from_rgba", "from_rgb8", "from_rgba8"]
+                .into_iter()
+                .find(|name| path_ends_with(&call.func, &["Color", name]))
+                .ok_or("unsupported function call")?;
+
+            match (constructor, args.as_slice()) {
+                ("from_rgb", [r, g, b]) => Ok(Color::from_rgb(*r, *g, *b)),
+                ("from_rgba", [r, g, b, a]) => Ok(Color::from_rgba(*r, *g, *b, *a)),
+                ("from_rgb8", [r, g, b]) => Ok(Color::from_rgb8(byte(*r)?, byte(*g)?, byte(*b)?)),
+                ("from_rgba8", [r, g, b, a]) => {
+                    Ok(Color::from_rgba8(byte(*r)?, byte(*g)?, byte(*b)?, *a))
+                }
+                _ => Err("wrong number of arguments"),
+            }
+        }
+        Expr::Macro(expr) if ends_with(&expr.mac.path, &["color"]) => {
+            let args = expr
+                .mac
+                .parse_body_with(
+                    syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated,
+                )
+                .map_err(|_| "unreadable color! arguments")?;
+            let args: Vec<f32> = args
+                .iter()
+                .map(number)
+                .collect::<Option<_>>()
+                .ok_or("arguments are not numeric literals")?;
+
+            let hex = |hex: f32| {
+                if hex > 0xff_ffff as f32 {
+                    return Err("hex color is larger than 0xffffff");
+                }
+                let hex = hex as u32;
+                Ok([(hex >> 16) as u8, (hex >> 8) as u8, hex as u8])
+            };
+            match args.as_slice() {
+                [value] => {
+                    let [r, g, b] = hex(*value)?;
+                    Ok(Color::from_rgb8(r, g, b))
+                }
+                [value, a] => {
+                    let [r, g, b] = hex(*value)?;
+                    Ok(Color::from_rgba8(r, g, b, *a))
+                }
+                [r, g, b] => Ok(Color::from_rgb8(byte(*r)?, byte(*g)?, byte(*b)?)),
+                [r, g, b, a] => Ok(Color::from_rgba8(byte(*r)?, byte(*g)?, byte(*b)?, *a)),
+                _ => Err("wrong number of arguments"),
+            }
+        }
+        Expr::Path(path) => {
+            match path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+            {
+                Some(name) if path_ends_with(expr, &["Color", &name]) => match name.as_str() {
+                    "BLACK" => Ok(Color::BLACK),
+                    "WHITE" => Ok(Color::WHITE),
+                    "TRANSPARENT" => Ok(Color::TRANSPARENT),
+                    _ => Err("unknown Color constant"),
+                },
+                _ => Err("refers to a variable or constant"),
+            }
+        }
+        Expr::Struct(literal) if ends_with(&literal.path, &["Color"]) => {
+            let channel = |name| field(literal, name).and_then(number);
+            match (channel("r"), channel("g"), channel("b"), channel("a")) {
+                (Some(r), Some(g), Some(b), Some(a)) => Ok(Color { r, g, b, a }),
+                _ => Err("channels are not numeric literals"),
+            }
+        }
+        Expr::Paren(paren) => resolve_color(&paren.expr),
+        _ => Err("unsupported expression"),
+    }
+}
+
+/// An 8-bit channel, `as u8` would wrap values like 300 to another color
+fn byte(value: f32) -> Result<u8, &'static str> {
+    if (0.0..=255.0).contains(&value) && value.fract() == 0.0 {
+        Ok(value as u8)
+    } else {
+        Err("channel is not an integer from 0 to 255")
+    }
+}
+
+fn number(expr: &Expr) -> Option<f32> {
+    match expr {
+        Expr::Lit(literal) => match &literal.lit {
+            Lit::Int(int) => int.base10_parse::<u32>().ok().map(|value| value as f32),
+            Lit::Float(float) => float.base10_parse::<f32>().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+
+    use super::*;
+
+    fn current() -> ([Color; 5], [[Color; 2]; 15]) {
+        (
+            arrays::populate_palette_array(Theme::Light.palette()),
+            arrays::populate_extended_array(Theme::Light.extended_palette()),
+        )
+    }
+
+    fn import_source(input: &str) -> SourceImport {
+        let (palette, extended) = current();
+        import(input, "source", (&palette, &extended)).unwrap()
+    }
+
+    const PALETTE: &str = r#"
+        const PALETTE: Palette = Palette {
+            background: Color::from_rgb(0.1, 0.2, 0.3),
+            primary: Color::from_rgb8(0x5f, 0x81, 0x9d),
+            text: color!(0xc5c8c6),
+            success: color!(0x8c9440, 0.5),
+            danger: iced::Color::from_rgba8(165, 66, 66, 0.8),
+        };
+    "#;
+
+    #[test]
+    fn reads_color_constructors_and_macros() {
+        let import = import_source(PALETTE);
+        let palette = import.document.palette;
+
+        assert_eq!(palette[0], Color::from_rgb(0.1, 0.2, 0.3));
+        assert_eq!(palette[1], Color::from_rgb8(0x5f, 0x81, 0x9d));
+        assert_eq!(palette[2], Color::from_rgb8(0xc5, 0xc8, 0xc6));
+        assert_eq!(palette[3], Color::from_rgba8(0x8c, 0x94, 0x40, 0.5));
+        assert_eq!(palette[4], Color::from_rgba8(165, 66, 66, 0.8));
+        assert!(!import
+            .unresolved
+            .iter()
+            .any(|line| line.starts_with("palette")));
+    }
+
+    #[test]
+    fn missing_extended_is_generated_from_the_palette() {
+        let import = import_source(PALETTE);
+        let generated = Extended::generate(arrays::palette_from_array(&import.document.palette));
+
+        assert_eq!(
+            import.document.extended,
+            arrays::populate_extended_array(&generated)
+        );
+        assert_eq!(
+            import.unresolved,
+            ["no Extended literal, it was generated from the palette"]
+        );
+    }
+
+    #[test]
+    fn pair_new_only_resolves_the_color() {
+        let import = import_source(
+            r#"
+            fn extended() -> Extended {
+                Extended {
+                    background: Background {
+                        base: Pair::new(color!(0x1d1f21), PALETTE.text),
+                        weak: Pair {
+                            color: Color::from_rgb8(0x28, 0x2a, 0x2e),
+                            text: Color::WHITE,
+                        },
+                    },
+                    is_dark: true,
+                }
+            }
+            "#,
+        );
+        let (palette, _) = current();
+
+        assert_eq!(import.document.palette, palette);
+        assert_eq!(
+            import.document.extended[0][0],
+            Color::from_rgb8(0x1d, 0x1f, 0x21)
+        );
+        assert_eq!(
+            import.document.extended[1],
+            [Color::from_rgb8(0x28, 0x2a, 0x2e), Color::WHITE]
+        );
+        assert!(import.unresolved.contains(
+            &"extended.background.base.text: computed by Pair::new at runtime".to_string()
+        ));
+        assert!(import
+            .unresolved
+            .contains(&"extended.background.strong.color: missing".to_string()));
+        assert!(import
+            .unresolved
+            .contains(&"no Palette literal, the current palette was kept".to_string()));
+    }
+
+    #[test]
+    fn variables_and_out_of_range_channels_are_unresolved() {
+        let import = import_source(
+            r#"
+            const PALETTE: Palette = Palette {
+                background: BACKGROUND,
+                primary: Color::from_rgb8(300, 0, 0),
+                text: color!(0x1000000),
+                success: Color::from_rgba8(0, 128, 0.5, 1.0),
+                danger: Color::from_rgb(red, 0.0, 0.0),
+            };
+            "#,
+        );
+        let (palette, _) = current();
+
+        assert_eq!(import.document.palette, palette);
+        for line in [
+            "palette.background: refers to a variable or constant",
+            "palette.primary: channel is not an integer from 0 to 255",
+            "palette.text: hex color is larger than 0xffffff",
+            "palette.success: channel is not an integer from 0 to 255",
+            "palette.danger: arguments are not numeric literals",
+        ] {
+            assert!(
+                import.unresolved.contains(&line.to_string()),
+                "{line} in {:?}",
+                import.unresolved
+            );
+        }
+    }
+
+    #[test]
+    fn sources_without_literals_are_rejected() {
+        let (palette, extended) = current();
+        assert!(import("fn main() {}", "source", (&palette, &extended)).is_err());
+        assert!(import("fn main( {}", "source", (&palette, &extended)).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use iced::{
+    widget::{button, column, container, row, text, text_input, Column, Row},
+    Alignment, Background, Color, Element,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    color::{self, Gamut, Oklch},
+    Message, ThemeColors, ROLE_NAMES,
+};
+
+pub const STEPS: [u16; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
+/// OKLCH lightness of the lightest and darkest step, the steps in between are evenly spaced
+const LIGHTEST: f32 = 0.97;
+const DARKEST: f32 = 0.26;
+
+pub type Scales = [[Color; 11]; 5];
+
+/// The colors the scales are built from, one per Extended role
+pub fn seeds(palette: &[Color; 5], extended: &[[Color; 2]; 15]) -> [Color; 5] {
+    [
+        palette[0],
+        palette[1],
+        extended[6][0],
+        palette[3],
+        palette[4],
+    ]
+}
+
+pub fn generate(seeds: [Color; 5]) -> Scales {
+    seeds.map(|seed| {
+        let seed = Oklch::from_color(seed);
+        let mut ramp = [Color::BLACK; 11];
+
+        for (step, color) in ramp.iter_mut().enumerate() {
+            let l = LIGHTEST - (LIGHTEST - DARKEST) * step as f32 / 10.0;
+            // Very light and very dark tones can't hold much chroma, taper toward the ends
+            let distance = (l - 0.6).abs() / 0.4;
+            let c = seed.c * (1.0 - 0.5 * distance * distance);
+
+            *color = Gamut::Srgb.fit_chroma(Oklch { l, c, h: seed.h }, 1.0);
+        }
+
+        ramp
+    })
+}
+
+pub fn to_css(scales: &Scales) -> String {
+    let mut css = String::from(":root {\n");
+    for (role, ramp) in scales.iter().enumerate() {
+        for (step, color) in STEPS.iter().zip(ramp) {
+            css.push_str(&format!(
+                "  --{}-{}: {};\n",
+                ROLE_NAMES[role],
+                step,
+                color::to_hex(*color)
+            ));
+        }
+    }
+    css.push_str("}\n");
+    css
+}
+
+/// Design tokens in the `$type`/`$value` form of the W3C community group draft
+pub fn to_json(scales: &Scales) -> String {
+    let mut roles = Map::new();
+    for (role, ramp) in scales.iter().enumerate() {
+        let mut steps = Map::new();
+        for (step, color) in STEPS.iter().zip(ramp) {
+            steps.insert(
+                step.to_string(),
+                json!({ "$type": "color", "$value": color::to_hex(*color) }),
+            );
+        }
+        roles.insert(ROLE_NAMES[role].to_string(), Value::Object(steps));
+    }
+
+    serde_json::to_string_pretty(&json!({ "scale": roles })).expect("scales serialize to json")
+}
+
+pub fn to_rust(scales: &Scales) -> String {
+    let mut rust = String::from("use iced::{color, Color};\n");
+    for (role, ramp) in scales.iter().enumerate() {
+        rust.push('\n');
+        for (step, color) in STEPS.iter().zip(ramp) {
+            let [r, g, b, _] = color.into_rgba8();
+            rust.push_str(&format!(
+                "pub const {}_{}: Color = color!(0x{:02x}{:02x}{:02x});\n",
+                ROLE_NAMES[role].to_uppercase(),
+                step,
+                r,
+                g,
+                b
+            ));
+        }
+    }
+    rust
+}
+
+pub fn view(app: &ThemeColors) -> Element<'_, Message> {
+    let scales = generate(seeds(&app.palette, &app.extended));
+
+    let header = STEPS.iter().fold(
+        Row::new().spacing(4).push(text("").width(100)),
+        |row, step| {
+            row.push(
+                text(step)
+                    .width(60)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+        },
+    );
+
+    let mut grid = Column::new().spacing(4).push(header);
+    for (role, ramp) in scales.iter().enumerate() {
+        let mut ramp_row = Row::new()
+            .spacing(4)
+            .align_items(Alignment::Center)
+            .push(text(ROLE_NAMES[role]).width(100));
+
+        for color in ramp {
+            let swatch = container(
+                text(color::to_hex(*color))
+                    .size(11)
+                    .style(iced::theme::Text::Color(readable_on(*color))),
+            )
+            .width(60)
+            .height(40)
+            .center_x()
+            .center_y()
+            .style(container::Appearance {
+                background: Some(Background::Color(*color)),
+                ..Default::default()
+            });
+
+            ramp_row = ramp_row.push(
+                button(swatch)
+                    .padding(0)
+                    .style(iced::theme::Button::Text)
+                    .on_press(Message::PickScaleColor(*color)),
+            );
+        }
+        grid = grid.push(ramp_row);
+    }
+
+    let target = match app.selection.as_slice() {
+        [select] => select.name(),
+        selection => format!("{} selected colors", selection.len()),
+    };
+    let hint = text(format!("Click a step to use it for {}", target));
+
+    let exports = row!(
+        text_input("Export directory", &app.export_directory)
+            .on_input(Message::ExportDirectoryChanged)
+            .width(300),
+        button(text("Export CSS")).on_press(Message::ExportScales(ScaleFormat::Css)),
+        button(text("Export JSON")).on_press(Message::ExportScales(ScaleFormat::Json)),
+        button(text("Export Rust")).on_press(Message::ExportScales(ScaleFormat::Rust)),
+    )
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    column!(grid, hint, exports)
+        .spacing(15)
+        .align_items(Alignment::Center)
+        .into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleFormat {
+    Css,
+    Json,
+    Rust,
+}
+
+impl ScaleFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ScaleFormat::Css => "scales.css",
+            ScaleFormat::Json => "scales.tokens.json",
+            ScaleFormat::Rust => "scales.rs",
+        }
+    }
+
+    pub fn export(self, scales: &Scales) -> String {
+        match self {
+            ScaleFormat::Css => to_css(scales),
+            ScaleFormat::Json => to_json(scales),
+            ScaleFormat::Rust => to_rust(scales),
+        }
+    }
+}
+
+fn readable_on(background: Color) -> Color {
+    if color::relative_luminance(background) > 0.4 {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
@@ -0,0 +1,41 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Select {
+    Palette(usize),
+    Extended((usize, usize)),
+}
+
+pub const PALETTE_NAMES: [&str; 5] = ["background", "primary", "text", "success", "danger"];
+pub const ROLE_NAMES: [&str; 5] = ["background", "primary", "secondary", "success", "danger"];
+pub const STRENGTH_NAMES: [&str; 3] = ["base", "weak", "strong"];
+pub const PAIR_NAMES: [&str; 2] = ["color", "text"];
+
+impl Select {
+    /// Every palette and extended slot, in array order
+    pub fn all() -> impl Iterator<Item = Select> {
+        (0..5).map(Select::Palette).chain(
+            (0..15).flat_map(|index| (0..2).map(move |pair| Select::Extended((index, pair)))),
+        )
+    }
+
+    /// The color this slot is read against: the other half of an Extended pair, text for the
+    /// palette background and the palette background for every other palette color
+    pub fn contrast_partner(self) -> Select {
+        match self {
+            Select::Palette(0) => Select::Palette(2),
+            Select::Palette(_) => Select::Palette(0),
+            Select::Extended((index, pair)) => Select::Extended((index, 1 - pair)),
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Select::Palette(index) => format!("palette.{}", PALETTE_NAMES[index]),
+            Select::Extended((index, pair)) => format!(
+                "extended.{}.{}.{}",
+                ROLE_NAMES[index / 3],
+                STRENGTH_NAMES[index % 3],
+                PAIR_NAMES[pair]
+            ),
+        }
+    }
+}
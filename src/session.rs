@@ -0,0 +1,303 @@
+use std::{fs, io, path::PathBuf};
+
+use iced::{Color, Theme};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color,
+    document::{DocumentError, ThemeDocument},
+    history::Snapshot,
+    paths, runtime, Select,
+};
+
+const FILE_NAME: &str = "session.json";
+
+/// Everything needed to pick up editing where the last run left off
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub palette: [Color; 5],
+    pub extended: [[Color; 2]; 15],
+    pub palette_locked: [bool; 5],
+    pub extended_locked: [[bool; 2]; 15],
+    pub working_theme: Theme,
+    pub app_theme: Theme,
+    pub selected: Select,
+    pub selection: Vec<Select>,
+    pub undo: Vec<Snapshot>,
+    pub redo: Vec<Snapshot>,
+    pub file_path: String,
+}
+
+impl Session {
+    pub fn path() -> Option<PathBuf> {
+        paths::state_dir().map(|directory| directory.join(FILE_NAME))
+    }
+
+    /// `None` when there is no previous session to restore
+    pub fn load() -> Option<Result<Self, DocumentError>> {
+        let input = fs::read_to_string(Self::path()?).ok()?;
+        Some(Self::from_json(&input))
+    }
+
+    pub fn save(json: &str) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no state directory available")
+        })?;
+        paths::write_atomic(&path, json.as_bytes())
+    }
+
+    pub fn discard() {
+        if let Some(path) = Self::path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let file = SessionFile {
+            palette: self.palette.map(color::to_hex).to_vec(),
+            extended: self.extended.map(|pair| pair.map(color::to_hex)).to_vec(),
+            palette_locked: self.palette_locked.to_vec(),
+            extended_locked: self.extended_locked.to_vec(),
+            working_theme: ThemeEntry::from_theme(&self.working_theme),
+            app_theme: ThemeEntry::from_theme(&self.app_theme),
+            selected: self.selected.name(),
+            selection: self.selection.iter().map(|select| select.name()).collect(),
+            undo: self.undo.iter().map(SnapshotFile::from_snapshot).collect(),
+            redo: self.redo.iter().map(SnapshotFile::from_snapshot).collect(),
+            file_path: self.file_path.clone(),
+        };
+
+        serde_json::to_string(&file).expect("session serializes to json")
+    }
+
+    fn from_json(input: &str) -> Result<Self, DocumentError> {
+        let file: SessionFile =
+            serde_json::from_str(input).map_err(|error| DocumentError::Parse(error.to_string()))?;
+
+        let selection = file
+            .selection
+            .iter()
+            .map(|name| parse_select(name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            palette: parse_colors(&file.palette)?,
+            extended: parse_pairs(&file.extended)?,
+            palette_locked: file
+                .palette_locked
+                .try_into()
+                .map_err(|_| DocumentError::Parse("wrong number of palette locks".to_string()))?,
+            extended_locked: file
+                .extended_locked
+                .try_into()
+                .map_err(|_| DocumentError::Parse("wrong number of extended locks".to_string()))?,
+            working_theme: file.working_theme.to_theme()?,
+            app_theme: file.app_theme.to_theme()?,
+            selected: parse_select(&file.selected)?,
+            selection,
+            undo: parse_snapshots(&file.undo)?,
+            redo: parse_snapshots(&file.redo)?,
+            file_path: file.file_path,
+        })
+    }
+}
+
+fn parse_select(name: &str) -> Result<Select, DocumentError> {
+    Select::all()
+        .find(|select| select.name() == name)
+        .ok_or_else(|| DocumentError::Parse(format!("unknown slot {name:?}")))
+}
+
+fn parse_color(value: &str) -> Result<Color, DocumentError> {
+    color::from_hex(value).ok_or_else(|| DocumentError::InvalidColor {
+        slot: "session".to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn parse_colors(values: &[String]) -> Result<[Color; 5], DocumentError> {
+    let colors = values
+        .iter()
+        .map(|value| parse_color(value))
+        .collect::<Result<Vec<_>, _>>()?;
+    colors
+        .try_into()
+        .map_err(|_| DocumentError::Parse("wrong number of palette colors".to_string()))
+}
+
+fn parse_pairs(values: &[[String; 2]]) -> Result<[[Color; 2]; 15], DocumentError> {
+    let pairs = values
+        .iter()
+        .map(|[color, text]| Ok([parse_color(color)?, parse_color(text)?]))
+        .collect::<Result<Vec<_>, DocumentError>>()?;
+    pairs
+        .try_into()
+        .map_err(|_| DocumentError::Parse("wrong number of extended colors".to_string()))
+}
+
+fn parse_snapshots(snapshots: &[SnapshotFile]) -> Result<Vec<Snapshot>, DocumentError> {
+    snapshots
+        .iter()
+        .map(|snapshot| {
+            Ok(Snapshot {
+                palette: parse_colors(&snapshot.palette)?,
+                extended: parse_pairs(&snapshot.extended)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionFile {
+    palette: Vec<String>,
+    extended: Vec<[String; 2]>,
+    palette_locked: Vec<bool>,
+    extended_locked: Vec<[bool; 2]>,
+    working_theme: ThemeEntry,
+    app_theme: ThemeEntry,
+    selected: String,
+    selection: Vec<String>,
+    undo: Vec<SnapshotFile>,
+    redo: Vec<SnapshotFile>,
+    file_path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    palette: Vec<String>,
+    extended: Vec<[String; 2]>,
+}
+
+impl SnapshotFile {
+    fn from_snapshot(snapshot: &Snapshot) -> Self {
+        Self {
+            palette: snapshot.palette.map(color::to_hex).to_vec(),
+            extended: snapshot
+                .extended
+                .map(|pair| pair.map(color::to_hex))
+                .to_vec(),
+        }
+    }
+}
+
+/// Built-in themes are stored by name, custom themes as a whole theme document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ThemeEntry {
+    BuiltIn { name: String },
+    Custom { document: String },
+}
+
+impl ThemeEntry {
+    pub fn from_theme(theme: &Theme) -> Self {
+        if Theme::ALL.contains(theme) {
+            ThemeEntry::BuiltIn {
+                name: theme.to_string(),
+            }
+        } else {
+            ThemeEntry::Custom {
+                document: ThemeDocument::from_theme(theme).to_toml(),
+            }
+        }
+    }
+
+    pub fn to_theme(&self) -> Result<Theme, DocumentError> {
+        match self {
+            ThemeEntry::BuiltIn { name } => runtime::builtin(name)
+                .ok_or_else(|| DocumentError::Parse(format!("unknown theme {name:?}"))),
+            ThemeEntry::Custom { document } => Ok(ThemeDocument::from_toml(document)?.to_theme()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(theme: &Theme) -> Snapshot {
+        Snapshot {
+            palette: crate::arrays::populate_palette_array(theme.palette()),
+            extended: crate::arrays::populate_extended_array(theme.extended_palette()),
+        }
+    }
+
+    /// Sessions store 8-bit colors, so the snapshot is rounded the same way first
+    fn rounded(snapshot: Snapshot) -> Snapshot {
+        let round = |color: Color| color::from_hex(&color::to_hex(color)).unwrap();
+        Snapshot {
+            palette: snapshot.palette.map(round),
+            extended: snapshot.extended.map(|pair| pair.map(round)),
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let current = rounded(snapshot(&Theme::Nord));
+        let mut extended_locked = [[false; 2]; 15];
+        extended_locked[7] = [true, false];
+        let custom =
+            ThemeDocument::from_toml(&ThemeDocument::from_theme(&Theme::KanagawaWave).to_toml())
+                .unwrap()
+                .to_theme();
+
+        let session = Session {
+            palette: current.palette,
+            extended: current.extended,
+            palette_locked: [false, true, false, false, true],
+            extended_locked,
+            working_theme: custom.clone(),
+            app_theme: Theme::Dracula,
+            selected: Select::Extended((7, 1)),
+            selection: vec![
+                Select::Palette(1),
+                Select::Extended((7, 1)),
+                Select::Extended((14, 0)),
+            ],
+            undo: vec![
+                rounded(snapshot(&Theme::Light)),
+                rounded(snapshot(&Theme::Dark)),
+            ],
+            redo: vec![rounded(snapshot(&Theme::Oxocarbon))],
+            file_path: "/home/user/themes/nord.toml".to_string(),
+        };
+
+        let restored = Session::from_json(&session.to_json()).unwrap();
+
+        assert_eq!(restored.palette, session.palette);
+        assert_eq!(restored.extended, session.extended);
+        assert_eq!(restored.palette_locked, session.palette_locked);
+        assert_eq!(restored.extended_locked, session.extended_locked);
+        assert_eq!(restored.working_theme, custom);
+        assert_eq!(restored.app_theme, Theme::Dracula);
+        assert_eq!(restored.selected, session.selected);
+        assert_eq!(restored.selection, session.selection);
+        assert_eq!(restored.undo, session.undo);
+        assert_eq!(restored.redo, session.redo);
+        assert_eq!(restored.file_path, session.file_path);
+        assert_eq!(restored.to_json(), session.to_json());
+    }
+
+    #[test]
+    fn invalid_sessions_are_rejected() {
+        assert!(Session::from_json("{}").is_err());
+        assert!(Session::from_json("not json").is_err());
+
+        let session = Session {
+            palette: [Color::BLACK; 5],
+            extended: [[Color::WHITE; 2]; 15],
+            palette_locked: [false; 5],
+            extended_locked: [[false; 2]; 15],
+            working_theme: Theme::Dark,
+            app_theme: Theme::Dark,
+            selected: Select::Palette(0),
+            selection: vec![Select::Palette(0)],
+            undo: Vec::new(),
+            redo: Vec::new(),
+            file_path: String::new(),
+        };
+        let json = session
+            .to_json()
+            .replace("\"palette.background\"", "\"palette.missing\"");
+        assert!(Session::from_json(&json).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use iced::Color;
+
+use crate::{
+    document::{DocumentError, ThemeDocument},
+    Select,
+};
+
+const VERSION: u8 = 1;
+const DARK: u8 = 0b01;
+/// Every color is opaque, so the alpha bytes are left out
+const OPAQUE: u8 = 0b10;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes the theme as `version, flags, colors, name length, name, crc32` in unpadded base64url
+pub fn encode(document: &ThemeDocument) -> String {
+    let colors: Vec<[u8; 4]> = Select::all()
+        .map(|select| document.color(select).into_rgba8())
+        .collect();
+    let opaque = colors.iter().all(|color| color[3] == 255);
+
+    let mut flags = 0;
+    if document.is_dark {
+        flags |= DARK;
+    }
+    if opaque {
+        flags |= OPAQUE;
+    }
+
+    let mut bytes = vec![VERSION, flags];
+    for color in colors {
+        bytes.extend_from_slice(if opaque { &color[..3] } else { &color });
+    }
+
+    let mut end = document.name.len().min(255);
+    while !document.name.is_char_boundary(end) {
+        end -= 1;
+    }
+    let name = &document.name[..end];
+    bytes.push(name.len() as u8);
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(&crc32(&bytes).to_be_bytes());
+
+    to_base64(&bytes)
+}
+
+pub fn decode(code: &str) -> Result<ThemeDocument, DocumentError> {
+    let invalid = |reason: &str| DocumentError::Parse(format!("invalid share code: {reason}"));
+
+    let bytes = from_base64(code.trim()).ok_or_else(|| invalid("not base64url"))?;
+    if bytes.len() < 6 {
+        return Err(invalid("too short"));
+    }
+    let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(payload).to_be_bytes() != checksum {
+        return Err(invalid("checksum mismatch, the code may be incomplete"));
+    }
+
+    let (version, flags) = (payload[0], payload[1]);
+    if version != VERSION {
+        return Err(invalid(&format!("unsupported version {version}")));
+    }
+
+    let stride = if flags & OPAQUE != 0 { 3 } else { 4 };
+    let colors_end = 2 + stride * 35;
+    let name_length = *payload
+        .get(colors_end)
+        .ok_or_else(|| invalid("too short"))? as usize;
+    let name = payload
+        .get(colors_end + 1..colors_end + 1 + name_length)
+        .ok_or_else(|| invalid("too short"))?;
+
+    let mut document = ThemeDocument {
+        name: String::from_utf8_lossy(name).into_owned(),
+        is_dark: flags & DARK != 0,
+        palette: [Color::BLACK; 5],
+        extended: [[Color::BLACK; 2]; 15],
+    };
+
+    for (select, color) in Select::all().zip(payload[2..colors_end].chunks(stride)) {
+        let alpha = color.get(3).map_or(1.0, |alpha| *alpha as f32 / 255.0);
+        let color = Color::from_rgba8(color[0], color[1], color[2], alpha);
+        match select {
+            Select::Palette(index) => document.palette[index] = color,
+            Select::Extended((index1, index2)) => document.extended[index1][index2] = color,
+        }
+    }
+
+    Ok(document)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 4 / 3 + 3);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| {
+            group | (*byte as u32) << (16 - 8 * index)
+        });
+        for index in 0..chunk.len() + 1 {
+            output.push(ALPHABET[(group >> (18 - 6 * index)) as usize & 63] as char);
+        }
+    }
+    output
+}
+
+fn from_base64(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut group = 0u32;
+        for (index, character) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|c| c == character)? as u32;
+            group |= value << (18 - 6 * index);
+        }
+        for index in 0..chunk.len() - 1 {
+            bytes.push((group >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+
+    use super::*;
+
+    /// Share codes store 8-bit colors, so the document is rounded the same way first
+    fn document() -> ThemeDocument {
+        let document = ThemeDocument::from_theme(&Theme::CatppuccinMocha);
+        ThemeDocument::from_toml(&document.to_toml()).unwrap()
+    }
+
+    /// Replaces the payload of a code and signs it again
+    fn with_payload(code: &str, change: impl FnOnce(&mut Vec<u8>)) -> String {
+        let mut bytes = from_base64(code).unwrap();
+        bytes.truncate(bytes.len() - 4);
+        change(&mut bytes);
+        bytes.extend_from_slice(&crc32(&bytes).to_be_bytes());
+        to_base64(&bytes)
+    }
+
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn round_trips() {
+        let document = document();
+        let code = encode(&document);
+
+        assert!(code
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'));
+        assert_eq!(decode(&code).unwrap(), document);
+        assert_eq!(decode(&format!("  {code}\n")).unwrap(), document);
+    }
+
+    #[test]
+    fn opaque_themes_leave_out_alpha() {
+        let document = document();
+        let bytes = from_base64(&encode(&document)).unwrap();
+
+        assert_eq!(bytes[1], DARK | OPAQUE);
+        assert_eq!(bytes.len(), 2 + 3 * 35 + 1 + document.name.len() + 4);
+    }
+
+    #[test]
+    fn alpha_is_kept() {
+        let mut document = document();
+        document.extended[4][0].a = 128.0 / 255.0;
+        document.palette[0].a = 0.0;
+        let code = encode(&document);
+
+        assert_eq!(from_base64(&code).unwrap()[1] & OPAQUE, 0);
+        assert_eq!(decode(&code).unwrap(), document);
+    }
+
+    #[test]
+    fn corrupted_checksums_are_rejected() {
+        let code = encode(&document());
+        let mut corrupted = code.clone().into_bytes();
+        let last = corrupted.len() - 2;
+        corrupted[last] = if corrupted[last] == b'A' { b'B' } else { b'A' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+
+        let error = decode(&corrupted).unwrap_err().to_string();
+        assert!(error.contains("checksum mismatch"), "{error}");
+
+        let error = decode(&code[..code.len() - 8]).unwrap_err().to_string();
+        assert!(error.contains("checksum mismatch"), "{error}");
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected() {
+        let code = with_payload(&encode(&document()), |bytes| bytes[0] = VERSION + 1);
+
+        let error = decode(&code).unwrap_err().to_string();
+        assert!(error.contains("unsupported version 2"), "{error}");
+    }
+
+    #[test]
+    fn invalid_codes_are_rejected() {
+        assert!(decode("").is_err());
+        assert!(decode("not a share code!").is_err());
+        let truncated = with_payload(&encode(&document()), |bytes| bytes.truncate(50));
+        assert!(decode(&truncated)
+            .unwrap_err()
+            .to_string()
+            .contains("too short"));
+    }
+
+    #[test]
+    fn long_names_are_cut_on_a_char_boundary() {
+        let mut document = document();
+        document.name = "é".repeat(200);
+
+        let decoded = decode(&encode(&document)).unwrap();
+        assert_eq!(decoded.name, "é".repeat(127));
+
+        document.name = "a".repeat(300);
+        let decoded = decode(&encode(&document)).unwrap();
+        assert_eq!(decoded.name, "a".repeat(255));
+    }
+
+    #[test]
+    fn non_ascii_names_round_trip() {
+        let mut document = document();
+        document.name = "Mocha ☕ テーマ".to_string();
+        assert_eq!(decode(&encode(&document)).unwrap(), document);
+    }
+}
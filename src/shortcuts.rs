@@ -0,0 +1,58 @@
+use iced::keyboard::{key::Named, Key, Modifiers};
+
+use crate::{Channel, Message};
+
+const STEP: f32 = 0.01;
+const COARSE_STEP: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Maps key presses that were not captured by a widget to editor messages
+///
+/// Arrows move the selection, Tab toggles between the color and text of an Extended pair,
+/// R/G/B/A and H/S/L pick the channel that `=`/`+` and `-` change, Shift makes the step coarse
+pub fn handle_key_press(key: Key, modifiers: Modifiers) -> Option<Message> {
+    if modifiers.command() {
+        return match key.as_ref() {
+            Key::Character("z") if modifiers.shift() => Some(Message::Redo),
+            Key::Character("z") => Some(Message::Undo),
+            Key::Character("y") => Some(Message::Redo),
+            Key::Character("c") => Some(Message::CopySelected),
+            Key::Character("v") => Some(Message::PasteSelected),
+            Key::Character("r") if modifiers.shift() => Some(Message::ResetAll),
+            Key::Character("r") => Some(Message::ResetSelected),
+            Key::Character("g") => Some(Message::GenerateFromBase),
+            Key::Character("t") => Some(Message::TryTheme),
+            _ => None,
+        };
+    }
+
+    let step = if modifiers.shift() { COARSE_STEP } else { STEP };
+
+    match key.as_ref() {
+        Key::Named(Named::ArrowUp) => Some(Message::Navigate(Direction::Up)),
+        Key::Named(Named::ArrowDown) => Some(Message::Navigate(Direction::Down)),
+        Key::Named(Named::ArrowLeft) => Some(Message::Navigate(Direction::Left)),
+        Key::Named(Named::ArrowRight) => Some(Message::Navigate(Direction::Right)),
+        Key::Named(Named::Tab) => Some(Message::ToggleSelectedPair),
+        Key::Character(character) => match character.to_lowercase().as_str() {
+            "r" => Some(Message::SelectChannel(Channel::Red)),
+            "g" => Some(Message::SelectChannel(Channel::Green)),
+            "b" => Some(Message::SelectChannel(Channel::Blue)),
+            "a" => Some(Message::SelectChannel(Channel::Alpha)),
+            "h" => Some(Message::SelectChannel(Channel::Hue)),
+            "s" => Some(Message::SelectChannel(Channel::Saturation)),
+            "l" => Some(Message::SelectChannel(Channel::Lightness)),
+            "+" | "=" => Some(Message::NudgeSelected(step)),
+            "-" | "_" => Some(Message::NudgeSelected(-step)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,159 @@
+use iced::{
+    theme,
+    widget::{button, column, container, pick_list, row, text, text_input, Column},
+    Alignment, Background, Color, Element, Length, Theme,
+};
+
+use crate::{color, contrast, document::ThemeDocument, Message, Select, ThemeColors};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+pub enum SourceMessage {
+    SelectTheme(Theme),
+    PathChanged(String),
+    LoadFile,
+}
+
+/// A theme picked for comparing or blending, either a built-in theme or a theme file
+pub struct SplitSide {
+    pub theme: Option<Theme>,
+    pub path: String,
+    pub document: ThemeDocument,
+    pub error: Option<String>,
+}
+
+impl SplitSide {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            document: ThemeDocument::from_theme(&theme),
+            theme: Some(theme),
+            path: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, message: SourceMessage) {
+        match message {
+            SourceMessage::SelectTheme(theme) => {
+                self.document = ThemeDocument::from_theme(&theme);
+                self.theme = Some(theme);
+                self.error = None;
+            }
+            SourceMessage::PathChanged(path) => self.path = path,
+            SourceMessage::LoadFile => match ThemeDocument::load(&self.path) {
+                Ok(document) => {
+                    self.document = document;
+                    self.theme = None;
+                    self.error = None;
+                }
+                Err(error) => self.error = Some(error.to_string()),
+            },
+        }
+    }
+}
+
+pub fn view(app: &ThemeColors) -> Element<'_, Message> {
+    let [left, right] = &app.split;
+
+    let sources = row!(
+        source_view(app, left, Side::Left, Message::SplitSource),
+        source_view(app, right, Side::Right, Message::SplitSource)
+    )
+    .spacing(40);
+
+    let labels = row!(
+        text("Slot").width(230),
+        text(&left.document.name).width(70),
+        text(&right.document.name).width(70),
+        text("ΔE2000").width(60),
+        text(format!("{} contrast", app.contrast_method.name())).width(150),
+        text("Take into working copy")
+    )
+    .spacing(10);
+
+    let mut rows = Column::new().spacing(4);
+    for select in Select::all() {
+        let left_color = left.document.color(select);
+        let right_color = right.document.color(select);
+        let delta_e = color::delta_e_2000(left_color, right_color);
+        let partner = select.contrast_partner();
+        let method = app.contrast_method;
+        let contrast = |document: &ThemeDocument, color: Color| {
+            let partner = document.color(partner);
+            let value = match select {
+                Select::Palette(0) | Select::Extended((_, 0)) => method.measure(partner, color),
+                _ => method.measure(color, partner),
+            };
+            method.format(value)
+        };
+        let left_contrast = contrast(&left.document, left_color);
+        let right_contrast = contrast(&right.document, right_color);
+
+        rows = rows.push(
+            row!(
+                text(select.name()).width(230),
+                split_swatch(left_color),
+                split_swatch(right_color),
+                text(format!("{delta_e:.2}")).width(60),
+                text(format!("{left_contrast} / {right_contrast}")).width(150),
+                button(text("Left").size(14))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::TakeSlot(Side::Left, select)),
+                button(text("Right").size(14))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::TakeSlot(Side::Right, select)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    column!(sources, contrast::method_picker(app), labels, rows)
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .into()
+}
+
+pub fn source_view<'a>(
+    app: &'a ThemeColors,
+    side: &'a SplitSide,
+    which: Side,
+    on_message: fn(Side, SourceMessage) -> Message,
+) -> Column<'a, Message> {
+    let theme_picker = pick_list(app.themes.as_slice(), side.theme.as_ref(), move |theme| {
+        on_message(which, SourceMessage::SelectTheme(theme))
+    });
+
+    let file = row!(
+        text_input("Theme file", &side.path)
+            .on_input(move |path| on_message(which, SourceMessage::PathChanged(path)))
+            .on_submit(on_message(which, SourceMessage::LoadFile))
+            .width(220),
+        button(text("Load")).on_press(on_message(which, SourceMessage::LoadFile))
+    )
+    .spacing(5);
+
+    let mut content = column!(theme_picker, file).spacing(5).width(Length::Shrink);
+    if let Some(error) = &side.error {
+        content = content.push(text(error).size(14).style(theme::Text::Color(
+            app.app_theme.extended_palette().danger.base.color,
+        )));
+    }
+    content
+}
+
+fn split_swatch<'a>(color: Color) -> Element<'a, Message> {
+    container(column!())
+        .width(70)
+        .height(24)
+        .style(container::Appearance {
+            background: Some(Background::Color(color)),
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,274 @@
+use iced::Color;
+
+use crate::{
+    color::{self, Deficiency, Gamut, Hsl, Oklch},
+    document::ThemeDocument,
+    Select, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES,
+};
+
+pub const FILE_NAME: &str = "style-guide.html";
+
+/// Palette slots are `--palette-<name>`, Extended slots `--<role>-<strength>` and `--<role>-<strength>-text`
+fn variable(select: Select) -> String {
+    match select {
+        Select::Palette(index) => format!("--palette-{}", PALETTE_NAMES[index]),
+        Select::Extended((index, pair)) => format!(
+            "--{}-{}{}",
+            ROLE_NAMES[index / 3],
+            STRENGTH_NAMES[index % 3],
+            if pair == 1 { "-text" } else { "" }
+        ),
+    }
+}
+
+fn rgb(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a < 255 {
+        format!("rgb({r} {g} {b} / {:.2})", color.a)
+    } else {
+        format!("rgb({r} {g} {b})")
+    }
+}
+
+fn hsl(color: Color) -> String {
+    let hsl = Hsl::from_color(color);
+    format!(
+        "hsl({:.0} {:.0}% {:.0}%)",
+        hsl.h,
+        hsl.s * 100.0,
+        hsl.l * 100.0
+    )
+}
+
+fn oklch(color: Color) -> String {
+    let lch = Oklch::from_color(color);
+    format!("oklch({:.1}% {:.3} {:.1})", lch.l * 100.0, lch.c, lch.h)
+}
+
+/// WCAG 2 level for normal sized text
+fn level(ratio: f32) -> &'static str {
+    if ratio >= 7.0 {
+        "AAA"
+    } else if ratio >= 4.5 {
+        "AA"
+    } else if ratio >= 3.0 {
+        "AA large"
+    } else {
+        "Fail"
+    }
+}
+
+fn display_p3(color: Color) -> String {
+    let [r, g, b] = color::to_display_p3(color).map(|channel| channel.clamp(0.0, 1.0));
+    if color.a < 1.0 {
+        format!("color(display-p3 {r:.4} {g:.4} {b:.4} / {:.2})", color.a)
+    } else {
+        format!("color(display-p3 {r:.4} {g:.4} {b:.4})")
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn swatch(color: Color, p3: bool) -> String {
+    let background = if p3 {
+        format!(
+            "{}; background: {}",
+            rgb(Gamut::Srgb.map(color)),
+            display_p3(color)
+        )
+    } else {
+        rgb(color)
+    };
+    format!("<span class=\"swatch\" style=\"background: {background}\"></span>")
+}
+
+fn contrast_cell(first: Color, second: Color) -> String {
+    let ratio = color::contrast_ratio(first, second);
+    let level = level(ratio);
+    format!(
+        "<td class=\"{}\">{ratio:.2} <small>{level}</small></td>",
+        level.replace(' ', "-").to_lowercase()
+    )
+}
+
+/// In Display P3 mode the variables are overridden with `color(display-p3 …)` where the browser supports
+/// it, the sRGB values stay as the fallback mapped by chroma
+pub fn to_html(document: &ThemeDocument, gamut: Gamut) -> String {
+    let name = escape(&document.name);
+    let mut html = String::new();
+
+    html.push_str(&format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{name} style guide</title>\n<style>\n:root {{\n"
+    ));
+    for select in Select::all() {
+        html.push_str(&format!(
+            "  {}: {};\n",
+            variable(select),
+            rgb(Gamut::Srgb.map(document.color(select)))
+        ));
+    }
+    html.push_str("}\n");
+    if gamut == Gamut::DisplayP3 {
+        html.push_str("@supports (color: color(display-p3 1 1 1)) {\n:root {\n");
+        for select in Select::all() {
+            html.push_str(&format!(
+                "  {}: {};\n",
+                variable(select),
+                display_p3(document.color(select))
+            ));
+        }
+        html.push_str("}\n}\n");
+    }
+    html.push_str(STYLE);
+    html.push_str("</style>\n</head>\n<body>\n");
+    html.push_str(&format!(
+        "<h1>{name}</h1>\n<p>{} theme</p>\n",
+        if document.is_dark { "Dark" } else { "Light" }
+    ));
+
+    let p3 = gamut == Gamut::DisplayP3;
+    html.push_str(&format!(
+        "<h2>Colors</h2>\n<table>\n<tr><th></th><th>Slot</th><th>Variable</th>\
+         <th>Hex</th><th>RGB</th><th>HSL</th><th>OKLCH</th>{}</tr>\n",
+        if p3 { "<th>Display P3</th>" } else { "" }
+    ));
+    for select in Select::all() {
+        let color = document.color(select);
+        let srgb = Gamut::Srgb.map(color);
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td>\
+             <td>{}</td><td>{}</td><td>{}</td>{}</tr>\n",
+            swatch(color, p3),
+            select.name(),
+            variable(select),
+            color::to_hex(srgb),
+            rgb(srgb),
+            hsl(srgb),
+            oklch(color),
+            if p3 {
+                format!("<td>{}</td>", display_p3(color))
+            } else {
+                String::new()
+            }
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Palette contrast</h2>\n<table>\n<tr><th></th>");
+    for name in PALETTE_NAMES {
+        html.push_str(&format!("<th>{name}</th>"));
+    }
+    html.push_str("</tr>\n");
+    for (row, first) in document.palette.iter().enumerate() {
+        html.push_str(&format!("<tr><th>{}</th>", PALETTE_NAMES[row]));
+        for (column, second) in document.palette.iter().enumerate() {
+            if row == column {
+                html.push_str("<td></td>");
+            } else {
+                html.push_str(&contrast_cell(*first, *second));
+            }
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(
+        "<h2>Extended contrast</h2>\n<table>\n<tr><th>Pair</th><th>Sample</th>\
+         <th>Text on color</th><th>Color on background</th></tr>\n",
+    );
+    for (index, pair) in document.extended.iter().enumerate() {
+        html.push_str(&format!(
+            "<tr><th>{}.{}</th><td><span class=\"sample\" style=\"background: {}; color: {}\">\
+             Sample text</span></td>{}{}</tr>\n",
+            ROLE_NAMES[index / 3],
+            STRENGTH_NAMES[index % 3],
+            rgb(pair[0]),
+            rgb(pair[1]),
+            contrast_cell(pair[0], pair[1]),
+            contrast_cell(pair[0], document.palette[0])
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Color vision deficiencies</h2>\n<table>\n<tr><th>Slot</th><th>Normal</th>");
+    for deficiency in Deficiency::ALL {
+        html.push_str(&format!("<th>{}</th>", deficiency.name()));
+    }
+    html.push_str("</tr>\n");
+    for select in Select::all().filter(|select| !matches!(select, Select::Extended((_, 1)))) {
+        let color = document.color(select);
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td>",
+            select.name(),
+            swatch(color, p3)
+        ));
+        for deficiency in Deficiency::ALL {
+            html.push_str(&format!(
+                "<td>{}</td>",
+                swatch(deficiency.simulate(color), false)
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(COMPONENTS);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+const STYLE: &str = "body {
+  margin: 2rem;
+  font-family: system-ui, sans-serif;
+  background: var(--palette-background);
+  color: var(--palette-text);
+}
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { padding: 0.3rem 0.6rem; text-align: left; border-bottom: 1px solid var(--background-strong); }
+.swatch { display: inline-block; width: 2.5rem; height: 1.5rem; border-radius: 4px; vertical-align: middle; }
+.sample { display: inline-block; padding: 0.2rem 0.6rem; border-radius: 4px; }
+.fail { color: var(--danger-base); }
+.components { display: flex; flex-wrap: wrap; gap: 1rem; align-items: flex-start; }
+.button { border: none; border-radius: 4px; padding: 0.5rem 1rem; font: inherit; cursor: pointer; }
+.primary { background: var(--primary-base); color: var(--primary-base-text); }
+.primary:hover { background: var(--primary-strong); color: var(--primary-strong-text); }
+.secondary { background: var(--secondary-base); color: var(--secondary-base-text); }
+.secondary:hover { background: var(--secondary-strong); color: var(--secondary-strong-text); }
+.success { background: var(--success-base); color: var(--success-base-text); }
+.success:hover { background: var(--success-strong); color: var(--success-strong-text); }
+.danger { background: var(--danger-base); color: var(--danger-base-text); }
+.danger:hover { background: var(--danger-strong); color: var(--danger-strong-text); }
+.card { background: var(--background-weak); color: var(--background-weak-text); padding: 1rem; border-radius: 6px; width: 18rem; }
+.input { background: var(--background-base); color: var(--background-base-text); border: 1px solid var(--background-strong); border-radius: 4px; padding: 0.4rem; font: inherit; }
+.input:focus { outline: 2px solid var(--primary-base); }
+.alert { padding: 0.6rem 1rem; border-radius: 4px; width: 18rem; }
+.alert.success { background: var(--success-weak); color: var(--success-weak-text); }
+.alert.danger { background: var(--danger-weak); color: var(--danger-weak-text); }
+a { color: var(--primary-base); }
+";
+
+const COMPONENTS: &str = "<h2>Components</h2>
+<div class=\"components\">
+  <button class=\"button primary\">Primary</button>
+  <button class=\"button secondary\">Secondary</button>
+  <button class=\"button success\">Success</button>
+  <button class=\"button danger\">Danger</button>
+</div>
+<div class=\"components\" style=\"margin-top: 1rem\">
+  <div class=\"card\">
+    <h3>Card</h3>
+    <p>Cards sit on the weak background with a <a href=\"#\">link</a> in the primary color.</p>
+    <input class=\"input\" placeholder=\"Text input\">
+  </div>
+  <div>
+    <p class=\"alert success\">Changes saved</p>
+    <p class=\"alert danger\">Something went wrong</p>
+  </div>
+</div>
+";
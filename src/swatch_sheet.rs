@@ -0,0 +1,224 @@
+use iced::Color;
+use resvg::{tiny_skia, usvg};
+
+use crate::{color, document::ThemeDocument, Select, ROLE_NAMES, STRENGTH_NAMES};
+
+const MARGIN: u32 = 24;
+const CELL_WIDTH: u32 = 180;
+const CELL_HEIGHT: u32 = 110;
+const GAP: u32 = 8;
+const TITLE_HEIGHT: u32 = 48;
+const SECTION_HEIGHT: u32 = 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Svg,
+    Png,
+}
+
+impl SheetFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SheetFormat::Svg => "swatches.svg",
+            SheetFormat::Png => "swatches.png",
+        }
+    }
+
+    pub fn export(self, document: &ThemeDocument) -> Result<Vec<u8>, String> {
+        let svg = to_svg(document);
+        match self {
+            SheetFormat::Svg => Ok(svg.into_bytes()),
+            SheetFormat::Png => to_png(&svg),
+        }
+    }
+}
+
+/// The palette as one row followed by the Extended grid, one column per role and one row per strength
+pub fn to_svg(document: &ThemeDocument) -> String {
+    let width = MARGIN * 2 + CELL_WIDTH * 5 + GAP * 4;
+    let extended_top = MARGIN + TITLE_HEIGHT + SECTION_HEIGHT + CELL_HEIGHT + GAP * 2;
+    let height = extended_top + SECTION_HEIGHT + (CELL_HEIGHT + GAP) * 3 + MARGIN;
+
+    let background = color::to_hex(document.palette[0]);
+    let foreground = color::to_hex(document.palette[2]);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\">\n"
+    );
+    svg.push_str(&format!(
+        "  <rect width=\"100%\" height=\"100%\" fill=\"{background}\"/>\n"
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{MARGIN}\" y=\"{}\" font-size=\"24\" fill=\"{foreground}\">{}</text>\n",
+        MARGIN + 24,
+        escape(&document.name)
+    ));
+
+    let palette_top = MARGIN + TITLE_HEIGHT;
+    svg.push_str(&section_label("Palette", palette_top, &foreground));
+    for index in 0..5 {
+        let x = MARGIN + index as u32 * (CELL_WIDTH + GAP);
+        svg.push_str(&cell(
+            document,
+            Select::Palette(index),
+            x,
+            palette_top + SECTION_HEIGHT,
+        ));
+    }
+
+    svg.push_str(&section_label("Extended", extended_top, &foreground));
+    for role in 0..5 {
+        for strength in 0..3 {
+            let x = MARGIN + role as u32 * (CELL_WIDTH + GAP);
+            let y = extended_top + SECTION_HEIGHT + strength as u32 * (CELL_HEIGHT + GAP);
+            svg.push_str(&cell(
+                document,
+                Select::Extended((role * 3 + strength, 0)),
+                x,
+                y,
+            ));
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn section_label(label: &str, y: u32, fill: &str) -> String {
+    format!(
+        "  <text x=\"{MARGIN}\" y=\"{}\" font-size=\"16\" fill=\"{fill}\">{label}</text>\n",
+        y + 18
+    )
+}
+
+/// A swatch filled with the slot color and labeled in its contrast partner
+fn cell(document: &ThemeDocument, select: Select, x: u32, y: u32) -> String {
+    let fill = document.color(select);
+    let ink = document.color(select.contrast_partner());
+    let contrast = color::contrast_ratio(fill, ink);
+
+    let label = match select {
+        Select::Extended((index, _)) => {
+            format!("{}.{}", ROLE_NAMES[index / 3], STRENGTH_NAMES[index % 3])
+        }
+        Select::Palette(_) => select.name(),
+    };
+    let hex = match select {
+        Select::Extended((index, _)) => format!(
+            "{} / {}",
+            color::to_hex(document.extended[index][0]),
+            color::to_hex(document.extended[index][1])
+        ),
+        Select::Palette(_) => color::to_hex(fill),
+    };
+
+    let text = |dy: u32, size: u32, content: &str| {
+        format!(
+            "    <text x=\"{}\" y=\"{}\" font-size=\"{size}\"{}>{}</text>\n",
+            x + 10,
+            y + dy,
+            paint(ink),
+            escape(content)
+        )
+    };
+
+    let mut cell = format!(
+        "  <g>\n    <rect x=\"{x}\" y=\"{y}\" width=\"{CELL_WIDTH}\" height=\"{CELL_HEIGHT}\" \
+         rx=\"6\"{}/>\n",
+        paint(fill)
+    );
+    cell.push_str(&text(22, 13, &label));
+    cell.push_str(&text(42, 12, &hex));
+    cell.push_str(&text(72, 20, "Aa Sample"));
+    cell.push_str(&text(96, 12, &format!("{contrast:.2}:1")));
+    cell.push_str("  </g>\n");
+    cell
+}
+
+/// `fill` and, for translucent colors, `fill-opacity`, the hex is then written without alpha so
+/// it is not applied twice
+fn paint(color: Color) -> String {
+    if color.a < 1.0 {
+        let opaque = Color { a: 1.0, ..color };
+        format!(
+            " fill=\"{}\" fill-opacity=\"{:.3}\"",
+            color::to_hex(opaque),
+            color.a
+        )
+    } else {
+        format!(" fill=\"{}\"", color::to_hex(color))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Rasterizes the sheet on the CPU, labels use whatever sans-serif font the system provides
+pub fn to_png(svg: &str) -> Result<Vec<u8>, String> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|error| error.to_string())?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| "the swatch sheet has no area".to_string())?;
+
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+
+    use super::*;
+
+    fn document() -> ThemeDocument {
+        let mut document = ThemeDocument::from_theme(&Theme::Dark);
+        document.palette[0] = Color::from_rgb8(0x20, 0x40, 0x60);
+        document.palette[1] = Color::from_rgba8(0xff, 0x80, 0x00, 0.5);
+        document.extended[4][1] = Color::from_rgba8(0x10, 0x20, 0x30, 0.25);
+        document
+    }
+
+    #[test]
+    fn alpha_is_only_in_fill_opacity() {
+        let svg = to_svg(&document());
+
+        assert!(svg.contains(" rx=\"6\" fill=\"#ff8000\" fill-opacity=\"0.500\"/>"));
+        assert!(svg.contains(" fill=\"#102030\" fill-opacity=\"0.250\">"));
+        assert!(!svg.contains("fill=\"#ff800080\""));
+        assert!(!svg.contains("fill=\"#10203040\""));
+    }
+
+    #[test]
+    fn translucent_cells_render_at_their_alpha() {
+        let document = document();
+        let png = to_png(&to_svg(&document)).unwrap();
+        let pixmap = tiny_skia::Pixmap::decode_png(&png).unwrap();
+
+        // Top right corner of the primary cell, clear of the rounded corner and the labels
+        let x = MARGIN + CELL_WIDTH + GAP + CELL_WIDTH - 20;
+        let y = MARGIN + TITLE_HEIGHT + SECTION_HEIGHT + 5;
+        let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+
+        let [background, fill] = [document.palette[0], document.palette[1]].map(Color::into_rgba8);
+        let alpha = 0.5;
+        for (channel, actual) in [pixel.red(), pixel.green(), pixel.blue()]
+            .into_iter()
+            .enumerate()
+        {
+            let expected =
+                background[channel] as f32 * (1.0 - alpha) + fill[channel] as f32 * alpha;
+            assert!(
+                (actual as f32 - expected).abs() <= 2.0,
+                "channel {channel} is {actual}, expected {expected}"
+            );
+        }
+    }
+}
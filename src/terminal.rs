@@ -0,0 +1,884 @@
+use std::{fmt, path::Path};
+
+use iced::{
+    theme::palette::Extended,
+    widget::{button, column, container, pick_list, radio, row, text, Column, Row},
+    Alignment, Background, Color, Element, Theme,
+};
+use serde_json::Value;
+
+use crate::{arrays, color, document::ThemeDocument, Message, PALETTE_NAMES};
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Terminal and editor color scheme formats that can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemeFormat {
+    Alacritty,
+    Kitty,
+    ITerm,
+    WindowsTerminal,
+    VsCode,
+}
+
+impl SchemeFormat {
+    /// Guesses the format from the extension, JSON files are told apart by their contents
+    pub fn detect(path: &Path, input: &str) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "itermcolors" => Some(SchemeFormat::ITerm),
+            "toml" => Some(SchemeFormat::Alacritty),
+            "conf" => Some(SchemeFormat::Kitty),
+            "json" | "jsonc" if input.contains("\"colors\"") => Some(SchemeFormat::VsCode),
+            "json" => Some(SchemeFormat::WindowsTerminal),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SchemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemeFormat::Alacritty => write!(f, "Alacritty"),
+            SchemeFormat::Kitty => write!(f, "Kitty"),
+            SchemeFormat::ITerm => write!(f, "iTerm2"),
+            SchemeFormat::WindowsTerminal => write!(f, "Windows Terminal"),
+            SchemeFormat::VsCode => write!(f, "VS Code"),
+        }
+    }
+}
+
+/// A color found in the scheme, known keys are renamed to `background`, `foreground`,
+/// `selection background`, `selection text`, `inactive`, `cursor` and the ANSI names
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceColor {
+    pub name: String,
+    pub color: Color,
+}
+
+pub fn parse(format: SchemeFormat, input: &str) -> Result<Vec<SourceColor>, String> {
+    let mut colors = Vec::new();
+
+    match format {
+        SchemeFormat::Alacritty => parse_alacritty(input, &mut colors)?,
+        SchemeFormat::Kitty => parse_kitty(input, &mut colors),
+        SchemeFormat::ITerm => parse_iterm(input, &mut colors),
+        SchemeFormat::WindowsTerminal => parse_windows_terminal(input, &mut colors)?,
+        SchemeFormat::VsCode => parse_vscode(input, &mut colors)?,
+    }
+
+    if colors.is_empty() {
+        return Err(format!("no colors found in the {} scheme", format));
+    }
+    Ok(colors)
+}
+
+fn push(colors: &mut Vec<SourceColor>, name: String, value: &str) {
+    let value = value.trim().trim_matches(['"', '\'']);
+    let value = value.strip_prefix("0x").unwrap_or(value);
+
+    if let Some(color) = color::from_hex(value) {
+        if !colors.iter().any(|source| source.name == name) {
+            colors.push(SourceColor { name, color });
+        }
+    }
+}
+
+fn ansi_name(index: usize) -> Option<String> {
+    match index {
+        0..=7 => Some(ANSI_NAMES[index].to_string()),
+        8..=15 => Some(format!("bright {}", ANSI_NAMES[index - 8])),
+        _ => None,
+    }
+}
+
+/// Alacritty's TOML configuration, the older YAML files are not read
+fn parse_alacritty(input: &str, colors: &mut Vec<SourceColor>) -> Result<(), String> {
+    let config: toml::Table = toml::from_str(input).map_err(|error| error.to_string())?;
+    let sections = config
+        .get("colors")
+        .and_then(toml::Value::as_table)
+        .ok_or_else(|| "no [colors] table".to_string())?;
+
+    for (section, values) in sections {
+        let Some(values) = values.as_table() else {
+            continue;
+        };
+        for (key, value) in values {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            let name = match (section.as_str(), key.as_str()) {
+                ("primary", "background") => "background".to_string(),
+                ("primary", "foreground") => "foreground".to_string(),
+                ("normal", _) => key.clone(),
+                ("bright", _) => format!("bright {key}"),
+                ("selection", "background") => "selection background".to_string(),
+                ("selection", "text") => "selection text".to_string(),
+                ("cursor", "cursor") => "cursor".to_string(),
+                _ => format!("{section} {key}"),
+            };
+            push(colors, name, value);
+        }
+    }
+    Ok(())
+}
+
+fn parse_kitty(input: &str, colors: &mut Vec<SourceColor>) {
+    for line in input.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        // Settings like `font_size 640` would otherwise parse as hex colors
+        if !is_kitty_color_key(key) {
+            continue;
+        }
+
+        let name = match key {
+            "selection_background" => "selection background".to_string(),
+            "selection_foreground" => "selection text".to_string(),
+            "inactive_tab_background" => "inactive".to_string(),
+            _ => match key
+                .strip_prefix("color")
+                .and_then(|index| index.parse().ok())
+            {
+                Some(index) => ansi_name(index).unwrap_or_else(|| key.to_string()),
+                None => key.replace('_', " "),
+            },
+        };
+        push(colors, name, value);
+    }
+}
+
+fn is_kitty_color_key(key: &str) -> bool {
+    let indexed = key
+        .strip_prefix("color")
+        .is_some_and(|index| index.parse::<u8>().is_ok());
+    let mark =
+        key.starts_with("mark") && (key.ends_with("_foreground") || key.ends_with("_background"));
+
+    indexed
+        || mark
+        || key.starts_with("selection_")
+        || key.contains("_tab_")
+        || key.ends_with("_border_color")
+        || matches!(
+            key,
+            "foreground"
+                | "background"
+                | "cursor"
+                | "cursor_text_color"
+                | "url_color"
+                | "visual_bell_color"
+                | "tab_bar_background"
+                | "tab_bar_margin_color"
+        )
+}
+
+/// iTerm2 property lists store every color as a dict of float components
+fn parse_iterm(input: &str, colors: &mut Vec<SourceColor>) {
+    let mut rest = input;
+
+    while let Some(start) = rest.find("<key>") {
+        rest = &rest[start + 5..];
+        let Some(end) = rest.find("</key>") else {
+            break;
+        };
+        let key = &rest[..end];
+        rest = rest[end + 6..].trim_start();
+
+        let Some(name) = key.strip_suffix(" Color") else {
+            continue;
+        };
+        let (Some(dict), Some(dict_end)) = (rest.strip_prefix("<dict>"), rest.find("</dict>"))
+        else {
+            continue;
+        };
+        let dict = &dict[..dict_end - 6];
+        rest = &rest[dict_end + 7..];
+
+        let component = |channel: &str| {
+            let after = &dict[dict.find(&format!("<key>{channel} Component</key>"))?..];
+            let value = &after[after.find("<real>")? + 6..after.find("</real>")?];
+            value.trim().parse::<f32>().ok()
+        };
+        let (Some(r), Some(g), Some(b)) = (component("Red"), component("Green"), component("Blue"))
+        else {
+            continue;
+        };
+        let color = Color::from_rgba(r, g, b, component("Alpha").unwrap_or(1.0));
+
+        let name = match name {
+            "Background" => "background".to_string(),
+            "Foreground" => "foreground".to_string(),
+            "Selection" => "selection background".to_string(),
+            "Selected Text" => "selection text".to_string(),
+            "Cursor" => "cursor".to_string(),
+            _ => match name
+                .strip_prefix("Ansi ")
+                .and_then(|index| index.parse().ok())
+            {
+                Some(index) => ansi_name(index).unwrap_or_else(|| name.to_lowercase()),
+                None => name.to_lowercase(),
+            },
+        };
+        push(colors, name, &color::to_hex(color));
+    }
+}
+
+/// Either a single scheme or a whole `settings.json`, in which case the first scheme is used
+fn parse_windows_terminal(input: &str, colors: &mut Vec<SourceColor>) -> Result<(), String> {
+    let root: Value = serde_json::from_str(&strip_jsonc(input)).map_err(|e| e.to_string())?;
+    let scheme = match root.get("schemes").and_then(Value::as_array) {
+        Some(schemes) => schemes.first().ok_or_else(|| "no schemes".to_string())?,
+        None => &root,
+    };
+    let scheme = scheme
+        .as_object()
+        .ok_or_else(|| "the scheme is not an object".to_string())?;
+
+    for (key, value) in scheme {
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        let name = match key.as_str() {
+            "selectionBackground" => "selection background".to_string(),
+            "cursorColor" => "cursor".to_string(),
+            "purple" => "magenta".to_string(),
+            "brightPurple" => "bright magenta".to_string(),
+            _ => match key.strip_prefix("bright") {
+                Some(color) => format!("bright {}", color.to_lowercase()),
+                None => key.to_string(),
+            },
+        };
+        push(colors, name, value);
+    }
+    Ok(())
+}
+
+fn parse_vscode(input: &str, colors: &mut Vec<SourceColor>) -> Result<(), String> {
+    let root: Value = serde_json::from_str(&strip_jsonc(input)).map_err(|e| e.to_string())?;
+    let workbench = root
+        .get("colors")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "no \"colors\" object".to_string())?;
+
+    for (key, value) in workbench {
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        let name = match key.as_str() {
+            "editor.background" => "background".to_string(),
+            "editor.foreground" => "foreground".to_string(),
+            "editor.selectionBackground" => "selection background".to_string(),
+            "editor.selectionForeground" => "selection text".to_string(),
+            "editor.inactiveSelectionBackground" => "inactive".to_string(),
+            "terminalCursor.foreground" => "cursor".to_string(),
+            _ => match key.strip_prefix("terminal.ansi") {
+                Some(ansi) => match ansi.strip_prefix("Bright") {
+                    Some(color) => format!("bright {}", color.to_lowercase()),
+                    None => ansi.to_lowercase(),
+                },
+                None => key.to_string(),
+            },
+        };
+        push(colors, name, value);
+    }
+    Ok(())
+}
+
+/// VS Code and Windows Terminal allow comments and trailing commas in their JSON
+fn strip_jsonc(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    output.push(c);
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedSource {
+    /// Extended is generated from the mapped palette, like the built-in themes
+    Generate,
+    /// Generated, except the background weak and strong colors, which come from the scheme
+    Map,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingSlot {
+    Palette(usize),
+    Weak,
+    Strong,
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportMessage {
+    Map(MappingSlot, String),
+    SelectExtended(ExtendedSource),
+}
+
+/// Which scheme color goes into which slot, filled in by heuristics and corrected by the user
+pub struct ImportMapping {
+    pub name: String,
+    pub format: SchemeFormat,
+    pub sources: Vec<SourceColor>,
+    pub palette: [Option<usize>; 5],
+    pub weak: Option<usize>,
+    pub strong: Option<usize>,
+    pub extended: ExtendedSource,
+}
+
+impl ImportMapping {
+    pub fn new(name: String, format: SchemeFormat, sources: Vec<SourceColor>) -> Self {
+        let find = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| sources.iter().position(|source| source.name == *name))
+        };
+
+        let palette = [
+            find(&["background"]),
+            find(&["blue", "bright blue"]),
+            find(&["foreground", "white"]),
+            find(&["green", "bright green"]),
+            find(&["red", "bright red"]),
+        ];
+        let weak = find(&["selection background", "inactive"]);
+        let strong = find(&["inactive", "bright black"]);
+        let extended = if weak.is_some() || strong.is_some() {
+            ExtendedSource::Map
+        } else {
+            ExtendedSource::Generate
+        };
+
+        Self {
+            name,
+            format,
+            sources,
+            palette,
+            weak,
+            strong,
+            extended,
+        }
+    }
+
+    pub fn update(&mut self, message: ImportMessage) {
+        match message {
+            ImportMessage::Map(slot, name) => {
+                let index = self.sources.iter().position(|source| source.name == name);
+                match slot {
+                    MappingSlot::Palette(slot) => self.palette[slot] = index,
+                    MappingSlot::Weak => self.weak = index,
+                    MappingSlot::Strong => self.strong = index,
+                }
+            }
+            ImportMessage::SelectExtended(extended) => self.extended = extended,
+        }
+    }
+
+    fn source(&self, index: Option<usize>) -> Option<&SourceColor> {
+        index.and_then(|index| self.sources.get(index))
+    }
+
+    /// Unmapped palette slots keep the colors of the built-in dark theme
+    pub fn document(&self) -> ThemeDocument {
+        let mut palette = arrays::populate_palette_array(Theme::Dark.palette());
+        for (color, index) in palette.iter_mut().zip(self.palette) {
+            if let Some(source) = self.source(index) {
+                *color = source.color;
+            }
+        }
+
+        let generated = Extended::generate(arrays::palette_from_array(&palette));
+        let mut extended = arrays::populate_extended_array(&generated);
+
+        if self.extended == ExtendedSource::Map {
+            let readable = |background: Color| {
+                if color::contrast_ratio(background, palette[2])
+                    >= color::contrast_ratio(background, palette[0])
+                {
+                    palette[2]
+                } else {
+                    palette[0]
+                }
+            };
+            if let Some(weak) = self.source(self.weak) {
+                let selection_text = self
+                    .sources
+                    .iter()
+                    .find(|source| source.name == "selection text")
+                    .filter(|_| weak.name == "selection background");
+                extended[1] = [
+                    weak.color,
+                    selection_text.map_or_else(|| readable(weak.color), |text| text.color),
+                ];
+            }
+            if let Some(strong) = self.source(self.strong) {
+                extended[2] = [strong.color, readable(strong.color)];
+            }
+        }
+
+        ThemeDocument {
+            name: self.name.clone(),
+            is_dark: generated.is_dark,
+            palette,
+            extended,
+        }
+    }
+}
+
+pub fn load(path: &str) -> Result<ImportMapping, String> {
+    let path = Path::new(path);
+    let input = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let format = SchemeFormat::detect(path, &input)
+        .ok_or_else(|| "unrecognized color scheme file extension".to_string())?;
+    let sources = parse(format, &input)?;
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format.to_string());
+
+    Ok(ImportMapping::new(name, format, sources))
+}
+
+pub fn mapping_view(mapping: &ImportMapping) -> Element<'_, Message> {
+    let names: Vec<String> = mapping
+        .sources
+        .iter()
+        .map(|source| source.name.clone())
+        .collect();
+
+    let slot_row = |label: &str, slot: MappingSlot, index: Option<usize>| {
+        let source = mapping.source(index);
+        row!(
+            text(label.to_string()).width(150),
+            pick_list(
+                names.clone(),
+                source.map(|source| source.name.clone()),
+                move |name| Message::MapImport(ImportMessage::Map(slot, name))
+            )
+            .placeholder("Keep default")
+            .width(250),
+            mapping_swatch(source.map(|source| source.color)),
+        )
+        .spacing(10)
+        .align_items(Alignment::Center)
+    };
+
+    let mut content = Column::new()
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .push(
+            text(format!(
+                "Map {} scheme \"{}\"",
+                mapping.format, mapping.name
+            ))
+            .size(18),
+        );
+
+    for (index, name) in PALETTE_NAMES.iter().enumerate() {
+        content = content.push(slot_row(
+            &format!("palette.{name}"),
+            MappingSlot::Palette(index),
+            mapping.palette[index],
+        ));
+    }
+
+    content = content.push(
+        row!(
+            text("Extended").width(150),
+            radio(
+                "Generate",
+                ExtendedSource::Generate,
+                Some(mapping.extended),
+                |source| Message::MapImport(ImportMessage::SelectExtended(source))
+            ),
+            radio(
+                "Map weak / strong",
+                ExtendedSource::Map,
+                Some(mapping.extended),
+                |source| Message::MapImport(ImportMessage::SelectExtended(source))
+            ),
+        )
+        .spacing(20),
+    );
+
+    if mapping.extended == ExtendedSource::Map {
+        content = content
+            .push(slot_row("background.weak", MappingSlot::Weak, mapping.weak))
+            .push(slot_row(
+                "background.strong",
+                MappingSlot::Strong,
+                mapping.strong,
+            ));
+    }
+
+    let document = mapping.document();
+    let preview = document
+        .palette
+        .into_iter()
+        .chain(document.extended.iter().map(|pair| pair[0]))
+        .fold(Row::new(), |row, color| {
+            row.push(mapping_swatch(Some(color)))
+        });
+
+    content
+        .push(preview)
+        .push(
+            row!(
+                button(text("Import")).on_press(Message::ApplyImport),
+                button(text("Cancel"))
+                    .style(iced::theme::Button::Secondary)
+                    .on_press(Message::CancelImport),
+            )
+            .spacing(10),
+        )
+        .into()
+}
+
+fn mapping_swatch<'a>(color: Option<Color>) -> Element<'a, Message> {
+    container(column!())
+        .width(20)
+        .height(20)
+        .style(container::Appearance {
+            background: color.map(Background::Color),
+            ..Default::default()
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALACRITTY: &str = r##"
+[colors.primary]
+background = "#1d1f21"
+foreground = "#c5c8c6"
+
+[colors.normal]
+black = "#282a2e"
+red = "#a54242"
+green = "#8c9440"
+blue = "#5f819d"
+
+[colors.bright]
+black = "0x373b41"
+
+[colors.selection]
+background = "#373b41"
+text = "#c5c8c6"
+"##;
+
+    const KITTY: &str = "
+# Tomorrow Night
+font_size 640
+scrollback_lines 2000
+background_opacity 0.9
+foreground #c5c8c6
+background #1d1f21
+selection_background #373b41
+color0 #282a2e
+color1 #a54242
+color2 #8c9440
+color4 #5f819d
+color8 #373b41
+inactive_tab_background #303030
+foo #112233
+";
+
+    const ITERM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.25882352941176473</real>
+		<key>Green Component</key>
+		<real>0.25882352941176473</real>
+		<key>Red Component</key>
+		<real>0.6470588235294118</real>
+	</dict>
+	<key>Background Color</key>
+	<dict>
+		<key>Alpha Component</key>
+		<real>1</real>
+		<key>Blue Component</key>
+		<real>0.12941176470588237</real>
+		<key>Green Component</key>
+		<real>0.12156862745098039</real>
+		<key>Red Component</key>
+		<real>0.11372549019607843</real>
+	</dict>
+	<key>Selection Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.2549019607843137</real>
+		<key>Green Component</key>
+		<real>0.23137254901960785</real>
+		<key>Red Component</key>
+		<real>0.21568627450980393</real>
+	</dict>
+</dict>
+</plist>
+"#;
+
+    const WINDOWS_TERMINAL: &str = r##"{
+    // A whole settings file, the first scheme is imported
+    "schemes": [
+        {
+            "name": "Tomorrow Night",
+            "background": "#1D1F21",
+            "foreground": "#C5C8C6",
+            "selectionBackground": "#373B41",
+            "blue": "#5F819D",
+            "green": "#8C9440",
+            "red": "#A54242",
+            "purple": "#85678F",
+            "brightBlack": "#373B41",
+        },
+    ],
+}"##;
+
+    const VS_CODE: &str = r##"{
+    "name": "Tomorrow Night",
+    "colors": {
+        "editor.background": "#1d1f21",
+        "editor.foreground": "#c5c8c6",
+        /* the terminal colors are used for the palette */
+        "terminal.ansiBlue": "#5f819d",
+        "terminal.ansiGreen": "#8c9440",
+        "terminal.ansiRed": "#a54242",
+        "terminal.ansiBrightBlack": "#373b41",
+        "editor.inactiveSelectionBackground": "#282a2e",
+        "editor.lineHighlightBorder": "#00000000",
+    },
+}"##;
+
+    fn hex(value: &str) -> Color {
+        color::from_hex(value).unwrap()
+    }
+
+    fn names(sources: &[SourceColor]) -> Vec<&str> {
+        sources.iter().map(|source| source.name.as_str()).collect()
+    }
+
+    fn mapped(mapping: &ImportMapping, index: Option<usize>) -> Option<(&str, Color)> {
+        mapping
+            .source(index)
+            .map(|source| (source.name.as_str(), source.color))
+    }
+
+    #[test]
+    fn detects_formats() {
+        let detect = |file: &str, input: &str| SchemeFormat::detect(Path::new(file), input);
+        assert_eq!(detect("a.toml", ALACRITTY), Some(SchemeFormat::Alacritty));
+        assert_eq!(detect("a.conf", KITTY), Some(SchemeFormat::Kitty));
+        assert_eq!(detect("a.itermcolors", ITERM), Some(SchemeFormat::ITerm));
+        assert_eq!(
+            detect("a.json", WINDOWS_TERMINAL),
+            Some(SchemeFormat::WindowsTerminal)
+        );
+        assert_eq!(detect("a.json", VS_CODE), Some(SchemeFormat::VsCode));
+        assert_eq!(detect("a.txt", KITTY), None);
+    }
+
+    #[test]
+    fn alacritty() {
+        let sources = parse(SchemeFormat::Alacritty, ALACRITTY).unwrap();
+        let mut found = names(&sources);
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            [
+                "background",
+                "black",
+                "blue",
+                "bright black",
+                "foreground",
+                "green",
+                "red",
+                "selection background",
+                "selection text",
+            ]
+        );
+
+        let mapping = ImportMapping::new("tomorrow".to_string(), SchemeFormat::Alacritty, sources);
+        assert_eq!(
+            mapped(&mapping, mapping.palette[0]),
+            Some(("background", hex("#1d1f21")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.palette[1]),
+            Some(("blue", hex("#5f819d")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.palette[2]),
+            Some(("foreground", hex("#c5c8c6")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.weak),
+            Some(("selection background", hex("#373b41")))
+        );
+        assert_eq!(mapping.extended, ExtendedSource::Map);
+
+        let document = mapping.document();
+        assert_eq!(document.palette[4], hex("#a54242"));
+        assert_eq!(document.extended[1], [hex("#373b41"), hex("#c5c8c6")]);
+        assert!(document.is_dark);
+    }
+
+    #[test]
+    fn kitty_skips_settings_that_are_not_colors() {
+        let sources = parse(SchemeFormat::Kitty, KITTY).unwrap();
+        assert_eq!(
+            names(&sources),
+            [
+                "foreground",
+                "background",
+                "selection background",
+                "black",
+                "red",
+                "green",
+                "blue",
+                "bright black",
+                "inactive",
+            ]
+        );
+        // Unknown keys are skipped even when their value looks like a color
+        assert!(!names(&sources).contains(&"foo"));
+
+        let mapping = ImportMapping::new("tomorrow".to_string(), SchemeFormat::Kitty, sources);
+        assert_eq!(
+            mapped(&mapping, mapping.palette[3]),
+            Some(("green", hex("#8c9440")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.strong),
+            Some(("inactive", hex("#303030")))
+        );
+    }
+
+    #[test]
+    fn iterm() {
+        let sources = parse(SchemeFormat::ITerm, ITERM).unwrap();
+        assert_eq!(
+            names(&sources),
+            ["red", "background", "selection background"]
+        );
+
+        let mapping = ImportMapping::new("tomorrow".to_string(), SchemeFormat::ITerm, sources);
+        assert_eq!(
+            mapped(&mapping, mapping.palette[0]),
+            Some(("background", hex("#1d1f21")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.palette[4]),
+            Some(("red", hex("#a54242")))
+        );
+        assert_eq!(mapping.palette[1], None);
+        assert_eq!(
+            mapped(&mapping, mapping.weak),
+            Some(("selection background", hex("#373b41")))
+        );
+    }
+
+    #[test]
+    fn windows_terminal() {
+        let sources = parse(SchemeFormat::WindowsTerminal, WINDOWS_TERMINAL).unwrap();
+        assert!(names(&sources).contains(&"magenta"));
+
+        let mapping = ImportMapping::new(
+            "tomorrow".to_string(),
+            SchemeFormat::WindowsTerminal,
+            sources,
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.palette[1]),
+            Some(("blue", hex("#5f819d")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.weak),
+            Some(("selection background", hex("#373b41")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.strong),
+            Some(("bright black", hex("#373b41")))
+        );
+    }
+
+    #[test]
+    fn vs_code() {
+        let sources = parse(SchemeFormat::VsCode, VS_CODE).unwrap();
+        assert!(names(&sources).contains(&"editor.lineHighlightBorder"));
+
+        let mapping = ImportMapping::new("tomorrow".to_string(), SchemeFormat::VsCode, sources);
+        assert_eq!(
+            mapped(&mapping, mapping.palette[2]),
+            Some(("foreground", hex("#c5c8c6")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.palette[3]),
+            Some(("green", hex("#8c9440")))
+        );
+        assert_eq!(
+            mapped(&mapping, mapping.weak),
+            Some(("inactive", hex("#282a2e")))
+        );
+        assert_eq!(mapping.weak, mapping.strong);
+    }
+
+    #[test]
+    fn schemes_without_colors_are_rejected() {
+        assert!(parse(SchemeFormat::Kitty, "font_size 640\n").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use iced::{theme::palette::Extended, Color};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    arrays, color,
+    document::{DocumentError, ThemeDocument},
+    Select, PAIR_NAMES, PALETTE_NAMES, ROLE_NAMES, STRENGTH_NAMES,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    /// W3C Design Tokens Community Group format, leaves use `$type` and `$value`
+    W3c,
+    /// Tokens Studio for Figma, a single token set with `type` and `value` leaves
+    TokensStudio,
+}
+
+impl TokenFormat {
+    pub fn file_name(self, name: &str) -> String {
+        match self {
+            TokenFormat::W3c => format!("{name}.tokens.json"),
+            TokenFormat::TokensStudio => format!("{name}.tokens-studio.json"),
+        }
+    }
+}
+
+/// Token path of a slot, `palette.<name>` or `extended.<role>.<strength>.<color|text>`
+fn token_path(select: Select) -> Vec<&'static str> {
+    match select {
+        Select::Palette(index) => vec!["palette", PALETTE_NAMES[index]],
+        Select::Extended((index, pair)) => vec![
+            "extended",
+            ROLE_NAMES[index / 3],
+            STRENGTH_NAMES[index % 3],
+            PAIR_NAMES[pair],
+        ],
+    }
+}
+
+pub fn export(document: &ThemeDocument, format: TokenFormat) -> String {
+    let mut tokens = Map::new();
+
+    for select in Select::all() {
+        let hex = color::to_hex(document.color(select));
+        let leaf = match format {
+            TokenFormat::W3c => json!({ "$type": "color", "$value": hex }),
+            TokenFormat::TokensStudio => json!({ "value": hex, "type": "color" }),
+        };
+
+        let path = token_path(select);
+        let (last, groups) = path.split_last().expect("token paths are not empty");
+        let mut group = &mut tokens;
+        for name in groups {
+            group = group
+                .entry(name.to_string())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("token groups are objects");
+        }
+        group.insert(last.to_string(), leaf);
+    }
+
+    let root = match format {
+        TokenFormat::W3c => Value::Object(tokens),
+        TokenFormat::TokensStudio => json!({
+            &document.name: tokens,
+            "$themes": [],
+            "$metadata": { "tokenSetOrder": [&document.name] },
+        }),
+    };
+
+    serde_json::to_string_pretty(&root).expect("tokens serialize to json")
+}
+
+/// Reads either format, Tokens Studio files are searched for the first set with a palette and
+/// `{group.token}` aliases are resolved
+pub fn import(input: &str, name: &str) -> Result<ThemeDocument, DocumentError> {
+    let root: Value =
+        serde_json::from_str(input).map_err(|error| DocumentError::Parse(error.to_string()))?;
+
+    let tokens = if root.get("palette").is_some() {
+        &root
+    } else {
+        root.as_object()
+            .and_then(|sets| {
+                sets.iter()
+                    .filter(|(key, _)| !key.starts_with('$'))
+                    .map(|(_, set)| set)
+                    .find(|set| set.get("palette").is_some())
+            })
+            .ok_or_else(|| DocumentError::Parse("no palette tokens found".to_string()))?
+    };
+
+    let mut document = ThemeDocument {
+        name: name.to_string(),
+        is_dark: true,
+        palette: [Color::BLACK; 5],
+        extended: [[Color::BLACK; 2]; 15],
+    };
+
+    // Aliases are looked up in the set first, then in every other set and at the root
+    let scopes: Vec<&Value> = std::iter::once(tokens)
+        .chain(
+            root.as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(key, _)| !key.starts_with('$'))
+                .map(|(_, set)| set),
+        )
+        .chain(std::iter::once(&root))
+        .collect();
+
+    for select in Select::all() {
+        let leaf = lookup(tokens, token_path(select))
+            .ok_or_else(|| DocumentError::MissingColor(select.name()))?;
+
+        let value = leaf_value(leaf).ok_or_else(|| DocumentError::MissingColor(select.name()))?;
+        let value = resolve(&scopes, value).map_err(|alias| {
+            DocumentError::Parse(format!(
+                "{} refers to {}, which is not a color token",
+                select.name(),
+                alias
+            ))
+        })?;
+
+        let color = color::from_hex(value).ok_or_else(|| DocumentError::InvalidColor {
+            slot: select.name(),
+            value: value.to_string(),
+        })?;
+
+        match select {
+            Select::Palette(index) => document.palette[index] = color,
+            Select::Extended((index1, index2)) => document.extended[index1][index2] = color,
+        }
+    }
+
+    document.is_dark = Extended::generate(arrays::palette_from_array(&document.palette)).is_dark;
+
+    Ok(document)
+}
+
+/// Longest chain of aliases that is followed, longer chains are most likely cycles
+const MAX_ALIAS_DEPTH: usize = 16;
+
+fn lookup<'a, 'b>(group: &'a Value, path: impl IntoIterator<Item = &'b str>) -> Option<&'a Value> {
+    path.into_iter()
+        .try_fold(group, |group, name| group.get(name))
+}
+
+fn leaf_value(leaf: &Value) -> Option<&str> {
+    leaf.get("$value")
+        .or_else(|| leaf.get("value"))
+        .and_then(Value::as_str)
+}
+
+/// Follows `{group.token}` references until a value that is not an alias, the unresolved alias is
+/// the error
+fn resolve<'a>(scopes: &[&'a Value], value: &'a str) -> Result<&'a str, &'a str> {
+    let mut value = value;
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let Some(path) = value
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+        else {
+            return Ok(value);
+        };
+        value = scopes
+            .iter()
+            .find_map(|scope| lookup(scope, path.split('.')))
+            .and_then(leaf_value)
+            .ok_or(value)?;
+    }
+    Err(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Theme;
+
+    use super::*;
+
+    /// A document with the colors the token files can hold, hex rounds them to 8 bits
+    fn document() -> ThemeDocument {
+        let document = ThemeDocument::from_theme(&Theme::TokyoNight);
+        ThemeDocument::from_toml(&document.to_toml()).unwrap()
+    }
+
+    #[test]
+    fn w3c_round_trips() {
+        let document = document();
+        let imported = import(&export(&document, TokenFormat::W3c), &document.name).unwrap();
+        assert_eq!(imported, document);
+    }
+
+    #[test]
+    fn tokens_studio_round_trips() {
+        let document = document();
+        let exported = export(&document, TokenFormat::TokensStudio);
+        let imported = import(&exported, &document.name).unwrap();
+        assert_eq!(imported, document);
+    }
+
+    #[test]
+    fn aliases_are_resolved() {
+        let document = document();
+        let mut root: Value = serde_json::from_str(&export(&document, TokenFormat::W3c)).unwrap();
+        root["brand"] = json!({ "blue": { "$type": "color", "$value": "#123456" } });
+        root["palette"]["primary"]["$value"] = json!("{brand.blue}");
+        root["extended"]["primary"]["base"]["color"]["$value"] = json!("{palette.primary}");
+
+        let imported = import(&root.to_string(), &document.name).unwrap();
+        let blue = color::from_hex("#123456").unwrap();
+        assert_eq!(imported.palette[1], blue);
+        assert_eq!(imported.extended[3][0], blue);
+    }
+
+    #[test]
+    fn aliases_into_other_token_sets_are_resolved() {
+        let document = document();
+        let mut root: Value =
+            serde_json::from_str(&export(&document, TokenFormat::TokensStudio)).unwrap();
+        root["core"] = json!({ "red": { "type": "color", "value": "#ab0000" } });
+        root[&document.name]["palette"]["danger"]["value"] = json!("{red}");
+
+        let imported = import(&root.to_string(), &document.name).unwrap();
+        assert_eq!(imported.palette[4], color::from_hex("#ab0000").unwrap());
+    }
+
+    #[test]
+    fn unresolved_aliases_are_reported() {
+        let document = document();
+        let mut root: Value = serde_json::from_str(&export(&document, TokenFormat::W3c)).unwrap();
+        root["palette"]["text"]["$value"] = json!("{color.missing}");
+        root["palette"]["success"]["$value"] = json!("{palette.danger}");
+        root["palette"]["danger"]["$value"] = json!("{palette.success}");
+
+        let error = import(&root.to_string(), &document.name).unwrap_err();
+        assert!(error.to_string().contains("{color.missing}"), "{error}");
+
+        root["palette"]["text"]["$value"] = json!("#ffffff");
+        let error = import(&root.to_string(), &document.name).unwrap_err();
+        assert!(error.to_string().contains("palette.success"), "{error}");
+    }
+}
@@ -0,0 +1,53 @@
+use iced::Color;
+
+use crate::color::{self, Oklch};
+
+/// How far a full temperature shift moves a color along the OKLab a/b axes
+const TEMPERATURE_A: f32 = 0.01;
+const TEMPERATURE_B: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// Hue rotation in degrees
+    pub hue: f32,
+    /// Chroma multiplier
+    pub chroma: f32,
+    /// Offset added to OKLCH lightness
+    pub lightness: f32,
+    /// Warm (positive) or cool (negative) shift, `-1.0..=1.0`
+    pub temperature: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            hue: 0.0,
+            chroma: 1.0,
+            lightness: 0.0,
+            temperature: 0.0,
+        }
+    }
+}
+
+impl Transform {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, color: Color) -> Color {
+        if self.is_identity() {
+            return color;
+        }
+
+        let mut lch = Oklch::from_color(color);
+        lch.h = (lch.h + self.hue).rem_euclid(360.0);
+        lch.c = (lch.c * self.chroma).max(0.0);
+        lch.l = (lch.l + self.lightness).clamp(0.0, 1.0);
+
+        let mut lab = lch.to_oklab();
+        lab.a += self.temperature * TEMPERATURE_A;
+        lab.b += self.temperature * TEMPERATURE_B;
+
+        color::clamp_to_srgb(lab.to_color(color.a))
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use iced::Subscription;
+
+use crate::{history::Snapshot, Message};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The theme file that was last opened or saved, and the colors it held at that point
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub saved: Snapshot,
+    modified: Option<SystemTime>,
+}
+
+impl WatchedFile {
+    pub fn new(path: impl AsRef<Path>, saved: Snapshot) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            modified: modified(&path),
+            path,
+            saved,
+        }
+    }
+
+    /// True once for every change of the modification time
+    pub fn poll(&mut self) -> bool {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Polls the modification time, so it works the same on every platform and network drives
+pub fn subscription(watched: Option<&WatchedFile>) -> Subscription<Message> {
+    match watched {
+        Some(_) => iced::time::every(POLL_INTERVAL).map(|_| Message::CheckWatchedFile),
+        None => Subscription::none(),
+    }
+}
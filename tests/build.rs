@@ -0,0 +1,132 @@
+use std::{env, fs, path::PathBuf};
+
+use iced::{Color, Theme};
+use theme_colors::{
+    build::{self, MIN_CONTRAST},
+    document::ThemeDocument,
+};
+
+/// The directory theme files are written to, which is also `OUT_DIR` for the generated code
+fn directory() -> PathBuf {
+    let directory = env::temp_dir().join(format!("theme_colors-build-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    env::set_var("OUT_DIR", &directory);
+    directory
+}
+
+fn save(document: &ThemeDocument, stem: &str) -> PathBuf {
+    let path = directory().join(format!("{stem}.toml"));
+    document.save(&path).unwrap();
+    path
+}
+
+#[test]
+fn extended_pairs_do_not_fail_builtin_themes() {
+    for (index, theme) in Theme::ALL.iter().enumerate() {
+        let path = save(
+            &ThemeDocument::from_theme(theme),
+            &format!("builtin-{index}"),
+        );
+        match theme {
+            // Its palette text only reaches 4.13:1
+            Theme::SolarizedLight => build::include_theme_with_contrast(&path, 4.0),
+            _ => build::include_theme(&path),
+        }
+        assert!(directory()
+            .join(format!("builtin-{index}.theme.rs"))
+            .exists());
+    }
+}
+
+#[test]
+fn include_theme_writes_the_generated_code() {
+    let path = save(&ThemeDocument::from_theme(&Theme::Nord), "nord");
+    build::include_theme(&path);
+
+    let generated = fs::read_to_string(directory().join("nord.theme.rs")).unwrap();
+    assert_eq!(
+        generated,
+        build::to_rust(&ThemeDocument::load(&path).unwrap())
+    );
+}
+
+#[test]
+#[should_panic(expected = "palette.text on palette.background has a contrast of 1.00:1")]
+fn unreadable_palette_text_fails_the_build() {
+    let mut document = ThemeDocument::from_theme(&Theme::Dark);
+    document.palette[2] = document.palette[0];
+    build::include_theme(save(&document, "unreadable"));
+}
+
+#[test]
+#[should_panic(expected = "is not a valid color")]
+fn invalid_colors_fail_the_build() {
+    let path = directory().join("invalid.toml");
+    let document = ThemeDocument::from_theme(&Theme::Dark).to_toml();
+    fs::write(&path, document.replacen("#", "#zz", 1)).unwrap();
+    build::include_theme(path);
+}
+
+#[test]
+fn check_contrast_lists_every_pair_below_the_minimum() {
+    let document = ThemeDocument::from_theme(&Theme::Light);
+    let problems = build::check_contrast(&document, MIN_CONTRAST);
+
+    assert!(problems.contains(
+        &"extended.success.strong.text on extended.success.strong.color has a contrast of \
+          3.92:1, at least 4.50:1 is needed"
+            .to_string()
+    ));
+    assert!(!problems
+        .iter()
+        .any(|problem| problem.starts_with("palette.text")));
+    assert!(build::check_contrast(&document, 1.0).is_empty());
+    assert_eq!(build::check_contrast(&document, 21.5).len(), 16);
+}
+
+#[test]
+fn to_rust_writes_every_color() {
+    let mut document = ThemeDocument::from_theme(&Theme::TokyoNight);
+    document.name = "Tokyo \"Night\"".to_string();
+    document.extended[14][1] = Color::from_rgba(0.25, 0.5, 0.75, 0.5);
+    let generated = build::to_rust(&document);
+
+    assert!(generated.contains("pub const NAME: &str = \"Tokyo \\\"Night\\\"\";"));
+    assert!(generated.contains("pub const IS_DARK: bool = true;"));
+    assert!(generated.contains("::iced::Color { r: 0.25, g: 0.5, b: 0.75, a: 0.5 }],"));
+    for color in document.palette {
+        assert!(generated.contains(&format!(
+            "::iced::Color {{ r: {:?}, g: {:?}, b: {:?}, a: {:?} }},",
+            color.r, color.g, color.b, color.a
+        )));
+    }
+
+    let file = syn::parse_file(&generated).unwrap();
+    let names: Vec<String> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Const(item) => Some(item.ident.to_string()),
+            syn::Item::Fn(item) => Some(item.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "NAME",
+            "IS_DARK",
+            "PALETTE",
+            "EXTENDED_COLORS",
+            "EXTENDED",
+            "theme"
+        ]
+    );
+}
+
+#[test]
+fn to_rust_keeps_the_stored_is_dark() {
+    let mut document = ThemeDocument::from_theme(&Theme::Dark);
+    document.is_dark = false;
+    assert!(build::to_rust(&document).contains("pub const IS_DARK: bool = false;"));
+}
@@ -0,0 +1,205 @@
+use iced::Color;
+use proptest::prelude::*;
+use theme_colors::color::{self, Gamut, Hsl, Hsv, Lab, Lch, Oklab, Oklch, Xyz};
+
+/// Largest channel difference that still rounds to the same 8-bit value
+const TOLERANCE: f32 = 1.0 / 512.0;
+
+fn srgb() -> impl Strategy<Value = Color> {
+    (0.0f32..=1.0, 0.0f32..=1.0, 0.0f32..=1.0, 0.0f32..=1.0).prop_map(|(r, g, b, a)| Color {
+        r,
+        g,
+        b,
+        a,
+    })
+}
+
+fn rgba8() -> impl Strategy<Value = Color> {
+    any::<[u8; 4]>().prop_map(|[r, g, b, a]| Color::from_rgba8(r, g, b, a as f32 / 255.0))
+}
+
+fn assert_close(expected: Color, actual: Color) -> Result<(), TestCaseError> {
+    for (expected, actual) in [
+        (expected.r, actual.r),
+        (expected.g, actual.g),
+        (expected.b, actual.b),
+        (expected.a, actual.a),
+    ] {
+        prop_assert!(
+            (expected - actual).abs() <= TOLERANCE,
+            "{:?} became {:?}",
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn transfer_function_round_trips(value in 0.0f32..=1.0) {
+        let round_trip = color::linear_to_srgb(color::srgb_to_linear(value));
+        prop_assert!((value - round_trip).abs() <= 1e-5);
+    }
+
+    #[test]
+    fn hex_round_trips(color in rgba8()) {
+        prop_assert_eq!(color::from_hex(&color::to_hex(color)), Some(color));
+    }
+
+    #[test]
+    fn hsl_round_trips(color in srgb()) {
+        assert_close(color, Hsl::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn hsv_round_trips(color in srgb()) {
+        assert_close(color, Hsv::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn xyz_round_trips(color in srgb()) {
+        assert_close(color, Xyz::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn lab_round_trips(color in srgb()) {
+        assert_close(color, Lab::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn lch_round_trips(color in srgb()) {
+        assert_close(color, Lch::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn oklab_round_trips(color in srgb()) {
+        assert_close(color, Oklab::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn oklch_round_trips(color in srgb()) {
+        assert_close(color, Oklch::from_color(color).to_color(color.a))?;
+    }
+
+    #[test]
+    fn display_p3_round_trips(color in srgb()) {
+        let p3 = color::to_display_p3(color);
+        assert_close(color, color::from_display_p3(p3, color.a))?;
+    }
+
+    #[test]
+    fn srgb_is_inside_display_p3(color in srgb()) {
+        prop_assert!(Gamut::Srgb.contains(color));
+        prop_assert!(Gamut::DisplayP3.contains(color));
+    }
+
+    #[test]
+    fn gamut_mapping_keeps_lightness_and_hue(
+        l in 0.05f32..0.95,
+        c in 0.0f32..0.4,
+        h in 0.0f32..360.0,
+    ) {
+        let lch = Oklch { l, c, h };
+        for gamut in Gamut::ALL {
+            let mapped = gamut.fit_chroma(lch, 1.0);
+            prop_assert!(gamut.contains(mapped));
+
+            let mapped = Oklch::from_color(mapped);
+            prop_assert!((mapped.l - l).abs() <= 0.01, "lightness {} became {}", l, mapped.l);
+            prop_assert!(mapped.c <= c + 0.001);
+            if mapped.c > 0.02 {
+                let hue_shift = (mapped.h - h + 180.0).rem_euclid(360.0) - 180.0;
+                prop_assert!(hue_shift.abs() <= 2.0, "hue {} became {}", h, mapped.h);
+            }
+        }
+    }
+
+    #[test]
+    fn luminance_is_between_black_and_white(color in srgb()) {
+        let luminance = color::relative_luminance(color);
+        prop_assert!((-1e-6..=1.0 + 1e-6).contains(&luminance));
+    }
+
+    #[test]
+    fn contrast_is_symmetric_and_bounded(first in srgb(), second in srgb()) {
+        let ratio = color::contrast_ratio(first, second);
+        prop_assert_eq!(ratio, color::contrast_ratio(second, first));
+        prop_assert!((1.0..=21.0 + 1e-4).contains(&ratio));
+    }
+
+    #[test]
+    fn delta_e_is_zero_for_the_same_color(color in srgb()) {
+        prop_assert!(color::delta_e_76(color, color) <= 1e-4);
+        prop_assert!(color::delta_e_2000(color, color) <= 1e-4);
+    }
+
+    #[test]
+    fn delta_e_is_symmetric(first in srgb(), second in srgb()) {
+        let forward = color::delta_e_76(first, second);
+        prop_assert!((forward - color::delta_e_76(second, first)).abs() <= 1e-3);
+        let forward = color::delta_e_2000(first, second);
+        prop_assert!((forward - color::delta_e_2000(second, first)).abs() <= 1e-3);
+    }
+}
+
+#[test]
+fn delta_e_2000_matches_reference_pairs() {
+    // Sharma, Wu and Dalal (2005), pairs 1 and 17, converted from their Lab values
+    let pairs = [
+        (
+            Lab {
+                l: 50.0,
+                a: 2.6772,
+                b: -79.7751,
+            },
+            Lab {
+                l: 50.0,
+                a: 0.0,
+                b: -82.7485,
+            },
+            2.0425,
+        ),
+        (
+            Lab {
+                l: 50.0,
+                a: 2.5,
+                b: 0.0,
+            },
+            Lab {
+                l: 73.0,
+                a: 25.0,
+                b: -18.0,
+            },
+            27.1492,
+        ),
+    ];
+
+    for (first, second, expected) in pairs {
+        let difference = color::delta_e_2000(first.to_color(1.0), second.to_color(1.0));
+        assert!(
+            (difference - expected).abs() < 0.05,
+            "expected {expected}, got {difference}"
+        );
+    }
+}
+
+#[test]
+fn apca_matches_reference_values() {
+    let gray = Color::from_rgb8(0x88, 0x88, 0x88);
+    let pairs = [
+        (Color::BLACK, Color::WHITE, 106.04),
+        (Color::WHITE, Color::BLACK, -107.88),
+        (gray, Color::WHITE, 63.06),
+        (Color::WHITE, gray, -68.54),
+        (Color::WHITE, Color::WHITE, 0.0),
+    ];
+
+    for (text, background, expected) in pairs {
+        let contrast = color::apca_contrast(text, background);
+        assert!(
+            (contrast - expected).abs() < 0.1,
+            "expected {expected}, got {contrast}"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    theme_colors::build::include_theme("themes/fixture.toml");
+}
@@ -0,0 +1,42 @@
+//! Nothing but tests of the code `theme_colors::build` generated for `themes/fixture.toml`
+
+#[cfg(test)]
+mod tests {
+    use iced::theme::{palette::Extended, Palette};
+    use theme_colors::document::ThemeDocument;
+
+    mod fixture {
+        theme_colors::include_theme!("fixture");
+    }
+
+    // The generated items must be usable in const contexts
+    const PALETTE: Palette = fixture::PALETTE;
+    const EXTENDED: Extended = fixture::EXTENDED;
+
+    fn document() -> ThemeDocument {
+        ThemeDocument::load(concat!(env!("CARGO_MANIFEST_DIR"), "/themes/fixture.toml")).unwrap()
+    }
+
+    #[test]
+    fn generated_theme_matches_the_document() {
+        let document = document();
+        let theme = document.to_theme();
+
+        assert_eq!(fixture::NAME, document.name);
+        assert_eq!(fixture::IS_DARK, document.is_dark);
+        assert_eq!(PALETTE, theme.palette());
+        assert_eq!(&EXTENDED, theme.extended_palette());
+        assert_eq!(fixture::theme(), theme);
+    }
+
+    #[test]
+    fn generated_colors_match_the_file() {
+        let document = document();
+
+        assert_eq!(
+            theme_colors::arrays::populate_palette_array(PALETTE),
+            document.palette
+        );
+        assert_eq!(fixture::EXTENDED_COLORS, document.extended);
+    }
+}
@@ -0,0 +1,57 @@
+use iced::{theme::palette::Extended, Theme};
+use theme_colors::{arrays, document::ThemeDocument, runtime};
+
+/// A document the way the editor saves it, 8-bit colors and `is_dark` generated from the palette
+fn saved_document(theme: &Theme, name: &str) -> ThemeDocument {
+    let mut document = ThemeDocument::from_theme(theme);
+    document.name = name.to_string();
+    document.extended[4] = document.extended[13];
+    document.is_dark = Extended::generate(arrays::palette_from_array(&document.palette)).is_dark;
+    ThemeDocument::from_toml(&document.to_toml()).unwrap()
+}
+
+/// The theme the editor builds from its color arrays
+fn editor_theme(document: &ThemeDocument) -> Theme {
+    Theme::custom_with_fn(
+        document.name.clone(),
+        arrays::palette_from_array(&document.palette),
+        |palette| arrays::extended_from_colors(palette, &document.extended),
+    )
+}
+
+#[test]
+fn loaded_files_match_the_editor_theme() {
+    let directory =
+        std::env::temp_dir().join(format!("theme_colors-runtime-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    for theme in [Theme::Light, Theme::TokyoNight, Theme::CatppuccinLatte] {
+        let document = saved_document(&theme, &format!("{theme} edited"));
+        let path = directory.join(format!("{theme}.toml"));
+        document.save(&path).unwrap();
+
+        let loaded = runtime::load(&path).unwrap();
+        assert_eq!(loaded, editor_theme(&document), "{theme}");
+        assert_eq!(loaded, document.to_theme(), "{theme}");
+        assert_eq!(runtime::from_toml(&document.to_toml()).unwrap(), loaded);
+    }
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn stored_is_dark_is_kept() {
+    let mut document = saved_document(&Theme::Dark, "Dark");
+    document.is_dark = false;
+
+    let theme = runtime::from_toml(&document.to_toml()).unwrap();
+    assert!(!theme.extended_palette().is_dark);
+}
+
+#[test]
+fn missing_files_fall_back_to_a_builtin_theme() {
+    let missing = std::env::temp_dir().join("theme_colors-missing.toml");
+    assert_eq!(runtime::load_or(&missing, "Nord"), Theme::Nord);
+    assert_eq!(runtime::load_or(&missing, "No Such Theme"), Theme::Dark);
+    assert!(runtime::load(&missing).is_err());
+}